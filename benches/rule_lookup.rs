@@ -0,0 +1,35 @@
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use reqwest::Url;
+use reqwest_rewire::RewireClient;
+use std::collections::HashMap;
+
+fn build_redirects(rule_count: usize) -> HashMap<String, String> {
+    (0..rule_count)
+        .map(|i| {
+            (
+                format!("https://api-{}.example.com/service/{}/", i % 16, i),
+                format!("http://localhost:3000/mock/{}/", i),
+            )
+        })
+        .collect()
+}
+
+fn bench_rule_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rule_lookup");
+    for rule_count in [1, 10, 100, 1_000, 10_000] {
+        let client = RewireClient::new(build_redirects(rule_count));
+        let url = Url::parse(&format!(
+            "https://api-{}.example.com/service/{}/users/42?page=1",
+            (rule_count - 1) % 16,
+            rule_count - 1
+        ))
+        .unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(rule_count), &url, |b, url| {
+            b.iter(|| client.rules().rewrite(black_box(url)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_rule_lookup);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+use crate::BlockingTestableClient;
+use crate::env_rules::{DEFAULT_ENV_PREFIX, env_rules};
+use crate::{BlockingRewireClient, RewireError};
+use reqwest::blocking::Request;
+
+/// The blocking counterpart of [`Client`](crate::Client).
+#[derive(Clone)]
+pub enum BlockingClient {
+    ReqwestClient(reqwest::blocking::Client),
+    TestClient(BlockingRewireClient),
+}
+
+impl BlockingClient {
+    /// Creates a `BlockingRewireClient` from the rules of the `REQWEST_REWIRE_RULES` and `REQWEST_REWIRE_<n>`
+    /// environment variables, as [`BlockingRewireClient::from_env`](crate::BlockingRewireClient::from_env) does, or a plain
+    /// `reqwest::blocking::Client` when none are set.
+    pub fn from_env() -> Result<Self, RewireError> {
+        let rules = env_rules(DEFAULT_ENV_PREFIX)?;
+        if rules.is_empty() {
+            return Ok(BlockingClient::ReqwestClient(
+                reqwest::blocking::Client::new(),
+            ));
+        }
+        BlockingRewireClient::builder()
+            .rewires(rules)
+            .build()
+            .map(BlockingClient::TestClient)
+    }
+}
+
+#[cfg(not(tarpaulin))]
+impl BlockingTestableClient for BlockingClient {
+    fn reqwest_client(&self) -> &reqwest::blocking::Client {
+        match self {
+            BlockingClient::ReqwestClient(client) => client,
+            BlockingClient::TestClient(rewire_client) => rewire_client.reqwest_client(),
+        }
+    }
+
+    fn rewire_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        match self {
+            BlockingClient::ReqwestClient(_) => Ok(()),
+            BlockingClient::TestClient(rewire_client) => rewire_client.rewire_request(request),
+        }
+    }
+
+    fn rewire_built_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        match self {
+            BlockingClient::ReqwestClient(_) => Ok(()),
+            BlockingClient::TestClient(rewire_client) => {
+                rewire_client.rewire_built_request(request)
+            }
+        }
+    }
+
+    fn rewire_executed_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        match self {
+            BlockingClient::ReqwestClient(_) => Ok(()),
+            BlockingClient::TestClient(rewire_client) => {
+                rewire_client.rewire_executed_request(request)
+            }
+        }
+    }
+}
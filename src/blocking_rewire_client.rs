@@ -0,0 +1,88 @@
+use crate::env_rules::env_rules;
+use crate::{
+    BlockingRewireClientBuilder, BlockingTestableClient, GenericRewireClient, RewireError,
+};
+use reqwest::blocking::Request;
+use std::collections::HashMap;
+
+/// The blocking counterpart of [`RewireClient`](crate::RewireClient), wrapping a
+/// `reqwest::blocking::Client` and sharing the same rule engine.
+pub type BlockingRewireClient = GenericRewireClient<reqwest::blocking::Client>;
+
+impl BlockingRewireClient {
+    /// Creates a client from `redirects`, ignoring invalid rules.
+    pub fn new(redirects: HashMap<String, String>) -> Self {
+        Self::builder()
+            .rewires(redirects)
+            .strict(false)
+            .build()
+            .expect("non-strict builds never fail")
+    }
+
+    /// Creates a client from `redirects`, failing if any rule is invalid instead of ignoring it.
+    pub fn try_new(redirects: HashMap<String, String>) -> Result<Self, RewireError> {
+        Self::builder().rewires(redirects).build()
+    }
+
+    /// Creates a client sending requests through `client`, ignoring invalid rules.
+    pub fn from_reqwest_client(
+        client: reqwest::blocking::Client,
+        redirects: HashMap<String, String>,
+    ) -> Self {
+        Self::builder()
+            .rewires(redirects)
+            .with_reqwest_client(client)
+            .strict(false)
+            .build()
+            .expect("non-strict builds never fail")
+    }
+
+    /// Creates a client from the configuration file at `path`, in TOML, JSON or YAML depending on
+    /// its extension. See [`RewireConfig`](crate::RewireConfig) for the file layout.
+    #[cfg(feature = "config")]
+    pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self, crate::ConfigError> {
+        let config = crate::RewireConfig::from_file(path)?;
+        Ok(Self::builder().config(config).build()?)
+    }
+
+    /// Creates a client from the `source->target` rules of the `{prefix}_RULES` environment variable,
+    /// separated by commas or newlines, then of `{prefix}_0`, `{prefix}_1`... in numeric order.
+    pub fn from_env(prefix: &str) -> Result<Self, RewireError> {
+        Self::builder().rewires(env_rules(prefix)?).build()
+    }
+
+    /// Creates a [`BlockingRewireClientBuilder`] to configure a client.
+    pub fn builder() -> BlockingRewireClientBuilder {
+        BlockingRewireClientBuilder::default()
+    }
+}
+
+impl BlockingTestableClient for BlockingRewireClient {
+    fn reqwest_client(&self) -> &reqwest::blocking::Client {
+        &self.client
+    }
+
+    /// Rewrites the URL of `request` with the most specific matching rule.
+    /// If no rule matches, the client's [`UnmatchedPolicy`](crate::UnmatchedPolicy) applies.
+    fn rewire_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        if let Some(rewired) =
+            self.rewired_url(request.method(), request.url(), request.headers())?
+        {
+            *request.url_mut() = rewired;
+        }
+        Ok(())
+    }
+
+    fn rewire_built_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        self.rewire_request(request)?;
+        self.rewired.insert(request.method(), request.url());
+        Ok(())
+    }
+
+    fn rewire_executed_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        if self.rewired.take(request.method(), request.url()) {
+            return Ok(());
+        }
+        self.rewire_request(request)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{BlockingTestableClient, Error};
+use reqwest::Version;
+use reqwest::blocking::{Body, Request, RequestBuilder, Response};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::fmt;
+use std::time::Duration;
+
+/// The blocking counterpart of [`RewireRequestBuilder`](crate::RewireRequestBuilder), returned by
+/// the request methods of [`BlockingTestableClientExt`](crate::BlockingTestableClientExt).
+///
+/// The request is rewired once, when it is built or sent, so rules can match on the method and
+/// headers set on the builder.
+#[must_use = "BlockingRewireRequestBuilder does nothing until you 'send' it"]
+pub struct BlockingRewireRequestBuilder<'a, C: ?Sized> {
+    client: &'a C,
+    builder: RequestBuilder,
+}
+
+impl<'a, C: BlockingTestableClient + ?Sized> BlockingRewireRequestBuilder<'a, C> {
+    pub(crate) fn new(client: &'a C, builder: RequestBuilder) -> Self {
+        Self { client, builder }
+    }
+
+    /// Adds a header to the request. See `reqwest::blocking::RequestBuilder::header`.
+    pub fn header<K, V>(self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.map(|builder| builder.header(key, value))
+    }
+
+    /// Adds a set of headers to the request. See `reqwest::blocking::RequestBuilder::headers`.
+    pub fn headers(self, headers: HeaderMap) -> Self {
+        self.map(|builder| builder.headers(headers))
+    }
+
+    /// Enables HTTP basic authentication. See `reqwest::blocking::RequestBuilder::basic_auth`.
+    pub fn basic_auth<U, P>(self, username: U, password: Option<P>) -> Self
+    where
+        U: fmt::Display,
+        P: fmt::Display,
+    {
+        self.map(|builder| builder.basic_auth(username, password))
+    }
+
+    /// Enables HTTP bearer authentication. See `reqwest::blocking::RequestBuilder::bearer_auth`.
+    pub fn bearer_auth<T: fmt::Display>(self, token: T) -> Self {
+        self.map(|builder| builder.bearer_auth(token))
+    }
+
+    /// Sets the request body. See `reqwest::blocking::RequestBuilder::body`.
+    pub fn body<T: Into<Body>>(self, body: T) -> Self {
+        self.map(|builder| builder.body(body))
+    }
+
+    /// Sets a timeout for the request. See `reqwest::blocking::RequestBuilder::timeout`.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.map(|builder| builder.timeout(timeout))
+    }
+
+    /// Appends `query` to the query string of the request URL. See
+    /// `reqwest::blocking::RequestBuilder::query`.
+    pub fn query<T: serde::Serialize + ?Sized>(self, query: &T) -> Self {
+        self.map(|builder| builder.query(query))
+    }
+
+    /// Sets a form body. See `reqwest::blocking::RequestBuilder::form`.
+    pub fn form<T: serde::Serialize + ?Sized>(self, form: &T) -> Self {
+        self.map(|builder| builder.form(form))
+    }
+
+    /// Sets a JSON body. See `reqwest::blocking::RequestBuilder::json`.
+    #[cfg(feature = "json")]
+    pub fn json<T: serde::Serialize + ?Sized>(self, json: &T) -> Self {
+        self.map(|builder| builder.json(json))
+    }
+
+    /// Sets a multipart/form-data body. See `reqwest::blocking::RequestBuilder::multipart`.
+    #[cfg(feature = "multipart")]
+    pub fn multipart(self, multipart: reqwest::blocking::multipart::Form) -> Self {
+        self.map(|builder| builder.multipart(multipart))
+    }
+
+    /// Sets the HTTP version of the request. See `reqwest::blocking::RequestBuilder::version`.
+    pub fn version(self, version: Version) -> Self {
+        self.map(|builder| builder.version(version))
+    }
+
+    /// Applies `f` to the wrapped `reqwest::blocking::RequestBuilder`, e.g. to call methods
+    /// of reqwest features not forwarded by this builder. The request is still rewired when built
+    /// or sent.
+    pub fn map(self, f: impl FnOnce(RequestBuilder) -> RequestBuilder) -> Self {
+        Self {
+            client: self.client,
+            builder: f(self.builder),
+        }
+    }
+
+    /// Attempts to clone the builder, which fails if the body is a stream.
+    pub fn try_clone(&self) -> Option<Self> {
+        Some(Self {
+            client: self.client,
+            builder: self.builder.try_clone()?,
+        })
+    }
+
+    /// Builds the request and rewires it, so it can be sent by any client, e.g. a plain
+    /// `reqwest::blocking::Client` or a middleware stack.
+    ///
+    /// The client remembers the request, so [`execute`](crate::BlockingTestableClientExt::execute) sends it as it is
+    /// instead of rewiring it a second time. A [`RewireError`](crate::RewireError) refusing the
+    /// request is returned as [`Error::Rewire`].
+    pub fn build(self) -> Result<Request, Error> {
+        let mut request = self.builder.build()?;
+        self.client.rewire_built_request(&mut request)?;
+        Ok(request)
+    }
+
+    /// Builds and rewires the request like [`build`](Self::build), also returning the
+    /// `reqwest::blocking::Client` embedded in the builder. See `reqwest::blocking::RequestBuilder::build_split`.
+    pub fn build_split(self) -> (reqwest::blocking::Client, Result<Request, Error>) {
+        let (client, request) = self.builder.build_split();
+        let request = request.map_err(Error::from).and_then(|mut request| {
+            self.client.rewire_built_request(&mut request)?;
+            Ok(request)
+        });
+        (client, request)
+    }
+
+    /// Rewires the request, then sends it.
+    ///
+    /// A [`RewireError`](crate::RewireError) refusing the request is returned as
+    /// [`Error::Rewire`], without sending anything.
+    pub fn send(self) -> Result<Response, Error> {
+        let mut request = self.builder.build()?;
+        self.client.rewire_request(&mut request)?;
+        Ok(self.client.reqwest_client().execute(request)?)
+    }
+}
+
+impl<C: ?Sized> fmt::Debug for BlockingRewireRequestBuilder<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.builder.fmt(f)
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{BlockingRewireRequestBuilder, Error, RewireError};
+use http::Method;
+use reqwest::IntoUrl;
+use reqwest::blocking::{Request, Response};
+
+/// The blocking counterpart of [`TestableClient`](crate::TestableClient), implemented by
+/// `reqwest::blocking::Client` and [`BlockingRewireClient`](crate::BlockingRewireClient).
+///
+/// This trait is object safe. The request methods are provided by [`BlockingTestableClientExt`].
+pub trait BlockingTestableClient {
+    /// The `reqwest::blocking::Client` requests are sent through.
+    fn reqwest_client(&self) -> &reqwest::blocking::Client;
+
+    /// Rewrites the URL of `request` according to the client's rules, or returns why it must not
+    /// be sent.
+    fn rewire_request(&self, request: &mut Request) -> Result<(), RewireError>;
+
+    /// Rewrites the URL of a request built to be sent later, as
+    /// [`rewire_request`](Self::rewire_request) does. A client remembering the requests it rewires
+    /// this way sends them as they are from [`execute`](BlockingTestableClientExt::execute), instead of rewiring them
+    /// a second time.
+    fn rewire_built_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        self.rewire_request(request)
+    }
+
+    /// Rewrites the URL of a request about to be executed, unless
+    /// [`rewire_built_request`](Self::rewire_built_request) rewired it already.
+    fn rewire_executed_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        self.rewire_request(request)
+    }
+}
+
+/// Request methods of a [`BlockingTestableClient`], accepting any [`IntoUrl`] like those of
+/// `reqwest::blocking::Client`.
+pub trait BlockingTestableClientExt: BlockingTestableClient {
+    /// Starts building a GET request to `url`, rewired if it matches the client's rules.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use reqwest_rewire::{BlockingRewireClient, BlockingTestableClientExt};
+    ///
+    /// let client = BlockingRewireClient::builder()
+    ///     .rewire("http://localhost:8080/example", "http://localhost:8080/rewired")
+    ///     .build()
+    ///     .unwrap();
+    /// client.get("http://localhost:8080/example").send().unwrap(); // hits http://localhost:8080/rewired
+    /// client.get("http://localhost:8080/example/nested_path").send().unwrap(); // hits http://localhost:8080/rewired/nested_path
+    /// client.get("http://localhost:8080/example?foo=bar").send().unwrap(); // hits http://localhost:8080/rewired?foo=bar
+    /// ```
+    fn get<U: IntoUrl>(&self, url: U) -> BlockingRewireRequestBuilder<'_, Self> {
+        self.request(Method::GET, url)
+    }
+
+    /// Starts building a POST request to `url`, rewired if it matches the client's rules.
+    fn post<U: IntoUrl>(&self, url: U) -> BlockingRewireRequestBuilder<'_, Self> {
+        self.request(Method::POST, url)
+    }
+
+    /// Starts building a PUT request to `url`, rewired if it matches the client's rules.
+    fn put<U: IntoUrl>(&self, url: U) -> BlockingRewireRequestBuilder<'_, Self> {
+        self.request(Method::PUT, url)
+    }
+
+    /// Starts building a PATCH request to `url`, rewired if it matches the client's rules.
+    fn patch<U: IntoUrl>(&self, url: U) -> BlockingRewireRequestBuilder<'_, Self> {
+        self.request(Method::PATCH, url)
+    }
+
+    /// Starts building a DELETE request to `url`, rewired if it matches the client's rules.
+    fn delete<U: IntoUrl>(&self, url: U) -> BlockingRewireRequestBuilder<'_, Self> {
+        self.request(Method::DELETE, url)
+    }
+
+    /// Starts building a HEAD request to `url`, rewired if it matches the client's rules.
+    fn head<U: IntoUrl>(&self, url: U) -> BlockingRewireRequestBuilder<'_, Self> {
+        self.request(Method::HEAD, url)
+    }
+
+    /// Starts building a `method` request to `url`, rewired if it matches the client's rules.
+    ///
+    /// The request is rewired once, when [built](BlockingRewireRequestBuilder::build) or
+    /// [sent](BlockingRewireRequestBuilder::send), so rules can match on the headers set on the
+    /// builder. Invalid URLs are left to reqwest, so the error surfaces when the request is built
+    /// or sent, as [`Error::Reqwest`]. A [`RewireError`] refusing the
+    /// request is returned as [`Error::Rewire`].
+    fn request<U: IntoUrl>(
+        &self,
+        method: Method,
+        url: U,
+    ) -> BlockingRewireRequestBuilder<'_, Self> {
+        BlockingRewireRequestBuilder::new(self, self.reqwest_client().request(method, url))
+    }
+
+    /// Rewires an already built `request`, then executes it like
+    /// `reqwest::blocking::Client::execute`.
+    ///
+    /// A [`RewireError`] refusing the request is returned as [`Error::Rewire`], without sending
+    /// anything.
+    fn execute(&self, mut request: Request) -> Result<Response, Error> {
+        self.rewire_executed_request(&mut request)?;
+        Ok(self.reqwest_client().execute(request)?)
+    }
+}
+
+impl<C: BlockingTestableClient + ?Sized> BlockingTestableClientExt for C {}
+
+impl<C: BlockingTestableClient + ?Sized> BlockingTestableClient for &C {
+    fn reqwest_client(&self) -> &reqwest::blocking::Client {
+        (**self).reqwest_client()
+    }
+
+    fn rewire_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_request(request)
+    }
+
+    fn rewire_built_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_built_request(request)
+    }
+
+    fn rewire_executed_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_executed_request(request)
+    }
+}
+
+impl<C: BlockingTestableClient + ?Sized> BlockingTestableClient for Box<C> {
+    fn reqwest_client(&self) -> &reqwest::blocking::Client {
+        (**self).reqwest_client()
+    }
+
+    fn rewire_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_request(request)
+    }
+
+    fn rewire_built_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_built_request(request)
+    }
+
+    fn rewire_executed_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_executed_request(request)
+    }
+}
+
+impl BlockingTestableClient for reqwest::blocking::Client {
+    fn reqwest_client(&self) -> &reqwest::blocking::Client {
+        self
+    }
+
+    fn rewire_request(&self, _request: &mut Request) -> Result<(), RewireError> {
+        Ok(())
+    }
+}
@@ -1,59 +1,57 @@
-use crate::RewireClient;
 use crate::TestableClient;
+use crate::env_rules::{DEFAULT_ENV_PREFIX, env_rules};
+use crate::{RewireClient, RewireError};
+use reqwest::Request;
 
+#[derive(Clone)]
 pub enum Client {
     ReqwestClient(reqwest::Client),
     TestClient(RewireClient),
 }
 
-#[cfg(not(tarpaulin))]
-impl TestableClient for Client {
-    fn get(&self, url: &str) -> reqwest::RequestBuilder {
-        match self {
-            Client::ReqwestClient(client) => client.get(url),
-            Client::TestClient(rewire_client) => rewire_client.get(url),
-        }
-    }
-
-    fn post(&self, url: &str) -> reqwest::RequestBuilder {
-        match self {
-            Client::ReqwestClient(client) => client.post(url),
-            Client::TestClient(rewire_client) => rewire_client.post(url),
-        }
-    }
-
-    fn put(&self, url: &str) -> reqwest::RequestBuilder {
-        match self {
-            Client::ReqwestClient(client) => client.put(url),
-            Client::TestClient(rewire_client) => rewire_client.put(url),
+impl Client {
+    /// Creates a `RewireClient` from the rules of the `REQWEST_REWIRE_RULES` and `REQWEST_REWIRE_<n>`
+    /// environment variables, as [`RewireClient::from_env`](crate::RewireClient::from_env) does, or a plain
+    /// `reqwest::Client` when none are set.
+    pub fn from_env() -> Result<Self, RewireError> {
+        let rules = env_rules(DEFAULT_ENV_PREFIX)?;
+        if rules.is_empty() {
+            return Ok(Client::ReqwestClient(reqwest::Client::new()));
         }
+        RewireClient::builder()
+            .rewires(rules)
+            .build()
+            .map(Client::TestClient)
     }
+}
 
-    fn patch(&self, url: &str) -> reqwest::RequestBuilder {
+#[cfg(not(tarpaulin))]
+impl TestableClient for Client {
+    fn reqwest_client(&self) -> &reqwest::Client {
         match self {
-            Client::ReqwestClient(client) => client.patch(url),
-            Client::TestClient(rewire_client) => rewire_client.patch(url),
+            Client::ReqwestClient(client) => client,
+            Client::TestClient(rewire_client) => rewire_client.reqwest_client(),
         }
     }
 
-    fn delete(&self, url: &str) -> reqwest::RequestBuilder {
+    fn rewire_request(&self, request: &mut Request) -> Result<(), RewireError> {
         match self {
-            Client::ReqwestClient(client) => client.delete(url),
-            Client::TestClient(rewire_client) => rewire_client.delete(url),
+            Client::ReqwestClient(_) => Ok(()),
+            Client::TestClient(rewire_client) => rewire_client.rewire_request(request),
         }
     }
 
-    fn head(&self, url: &str) -> reqwest::RequestBuilder {
+    fn rewire_built_request(&self, request: &mut Request) -> Result<(), RewireError> {
         match self {
-            Client::ReqwestClient(client) => client.head(url),
-            Client::TestClient(rewire_client) => rewire_client.head(url),
+            Client::ReqwestClient(_) => Ok(()),
+            Client::TestClient(rewire_client) => rewire_client.rewire_built_request(request),
         }
     }
 
-    fn request(&self, method: http::Method, url: &str) -> reqwest::RequestBuilder {
+    fn rewire_executed_request(&self, request: &mut Request) -> Result<(), RewireError> {
         match self {
-            Client::ReqwestClient(client) => client.request(method, url),
-            Client::TestClient(rewire_client) => rewire_client.request(method, url),
+            Client::ReqwestClient(_) => Ok(()),
+            Client::TestClient(rewire_client) => rewire_client.rewire_executed_request(request),
         }
     }
 }
@@ -0,0 +1,67 @@
+use crate::RewireError;
+use std::env;
+
+/// The prefix read by [`Client::from_env`](crate::Client::from_env) and
+/// [`BlockingClient::from_env`](crate::BlockingClient::from_env).
+pub const DEFAULT_ENV_PREFIX: &str = "REQWEST_REWIRE";
+
+/// Reads `source->target` rules from the environment.
+///
+/// The rules of the `{prefix}_RULES` list come first, separated by commas or newlines, followed
+/// by those of `{prefix}_0`, `{prefix}_1`... in numeric order. Other variables starting with the
+/// prefix are ignored.
+pub(crate) fn env_rules(prefix: &str) -> Result<Vec<(String, String)>, RewireError> {
+    let list_variable = format!("{}_RULES", prefix);
+    let mut indexed = Vec::new();
+    for (name, value) in env::vars_os() {
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        let Some(index) = name
+            .strip_prefix(prefix)
+            .and_then(|suffix| suffix.strip_prefix('_'))
+            .and_then(|index| index.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        indexed.push((index, name.to_string(), value));
+    }
+    indexed.sort_by_key(|(index, ..)| *index);
+
+    let mut rules = Vec::new();
+    if let Some(list) = env::var_os(&list_variable) {
+        let list = env_value(&list_variable, list)?;
+        for entry in list.split([',', '\n']).map(str::trim) {
+            if !entry.is_empty() {
+                rules.push(parse_rule(&list_variable, entry)?);
+            }
+        }
+    }
+    for (_, name, value) in indexed {
+        let value = env_value(&name, value)?;
+        rules.push(parse_rule(&name, &value)?);
+    }
+    Ok(rules)
+}
+
+fn env_value(variable: &str, value: std::ffi::OsString) -> Result<String, RewireError> {
+    value
+        .into_string()
+        .map_err(|value| RewireError::InvalidEnvRule {
+            variable: variable.to_string(),
+            value: value.to_string_lossy().into_owned(),
+        })
+}
+
+/// Splits a `source->target` entry, ignoring whitespace around the arrow.
+fn parse_rule(variable: &str, entry: &str) -> Result<(String, String), RewireError> {
+    match entry.split_once("->") {
+        Some((source, target)) if !source.trim().is_empty() && !target.trim().is_empty() => {
+            Ok((source.trim().to_string(), target.trim().to_string()))
+        }
+        _ => Err(RewireError::InvalidEnvRule {
+            variable: variable.to_string(),
+            value: entry.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+use url::ParseError;
+
+/// Errors raised while building the redirect rules of a [`RewireClient`](crate::RewireClient).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RewireError {
+    /// The source URL of a rule could not be parsed.
+    InvalidSource { url: String, error: ParseError },
+    /// The target URL of a rule could not be parsed.
+    InvalidTarget { url: String, error: ParseError },
+    /// A source or target URL cannot have a path appended to it (e.g. `mailto:` or `data:` URLs).
+    CannotBeABase { url: String },
+    /// The source URL of a rule has no host to match requests against.
+    MissingHost { url: String },
+    /// Two rules match exactly the same requests, e.g. `http://api/v1` and `http://api:80/v1/`.
+    DuplicateRule { source: String, existing: String },
+    /// An environment variable does not hold `source->target` rules.
+    InvalidEnvRule { variable: String, value: String },
+    /// A request matched no rule, and the client refuses to send unmatched requests.
+    Unmatched { url: String },
+    /// A host pattern given to `allow_host` or `deny_host` is neither a domain, a `*.` wildcard
+    /// domain, an IP address nor a CIDR network.
+    InvalidHostPattern { pattern: String },
+    /// A request was about to be sent to a host matching the denied `pattern`.
+    Blocked { url: String, pattern: String },
+    /// The host of a rule source has a `*` that is not a whole label, e.g. `tenant-*.api.com`.
+    InvalidWildcard { url: String },
+    /// The path of a rule source has a `{name*}` segment that is not the last one, or two
+    /// template segments with the same name.
+    InvalidTemplate { url: String },
+    /// A rule target has a `{placeholder}` that its source does not capture, e.g. `{2}` for a
+    /// source with a single `*` label.
+    UnknownPlaceholder { target: String, placeholder: String },
+    /// The pattern of a regex rule is not a valid regular expression.
+    InvalidRegex { pattern: String, message: String },
+    /// A header a rule requires has an invalid name or value.
+    InvalidHeader { name: String, value: String },
+}
+
+impl fmt::Display for RewireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RewireError::InvalidSource { url, error } => {
+                write!(f, "invalid rule source `{}`: {}", url, error)
+            }
+            RewireError::InvalidTarget { url, error } => {
+                write!(f, "invalid rule target `{}`: {}", url, error)
+            }
+            RewireError::CannotBeABase { url } => {
+                write!(f, "`{}` cannot be used as a base URL", url)
+            }
+            RewireError::MissingHost { url } => {
+                write!(f, "rule source `{}` has no host", url)
+            }
+            RewireError::DuplicateRule { source, existing } => {
+                write!(f, "rule source `{}` duplicates `{}`", source, existing)
+            }
+            RewireError::InvalidEnvRule { variable, value } => {
+                write!(
+                    f,
+                    "`{}` is not a `source->target` rule in `{}`",
+                    value, variable
+                )
+            }
+            RewireError::Unmatched { url } => {
+                write!(f, "no rule matches `{}`, so it was not sent", url)
+            }
+            RewireError::InvalidHostPattern { pattern } => {
+                write!(f, "invalid host pattern `{}`", pattern)
+            }
+            RewireError::Blocked { url, pattern } => {
+                write!(
+                    f,
+                    "`{}` matches the denied host `{}`, so it was not sent",
+                    url, pattern
+                )
+            }
+            RewireError::InvalidWildcard { url } => {
+                write!(
+                    f,
+                    "rule source `{}` has a `*` that is not a whole host label",
+                    url
+                )
+            }
+            RewireError::InvalidTemplate { url } => {
+                write!(f, "invalid path template in rule source `{}`", url)
+            }
+            RewireError::UnknownPlaceholder {
+                target,
+                placeholder,
+            } => {
+                write!(
+                    f,
+                    "rule target `{}` uses `{{{}}}`, which its source does not capture",
+                    target, placeholder
+                )
+            }
+            RewireError::InvalidRegex { pattern, message } => {
+                write!(f, "invalid rule pattern `{}`: {}", pattern, message)
+            }
+            RewireError::InvalidHeader { name, value } => {
+                write!(f, "invalid header condition `{}: {}`", name, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RewireError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RewireError::InvalidSource { error, .. } | RewireError::InvalidTarget { error, .. } => {
+                Some(error)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Errors returned when sending a request through a rewiring client.
+#[derive(Debug)]
+pub enum Error {
+    /// The client refused to send the request, e.g. because it matched no rule and the client
+    /// is in [`UnmatchedPolicy::Error`](crate::UnmatchedPolicy::Error) mode.
+    Rewire(RewireError),
+    /// reqwest failed to build or send the request.
+    Reqwest(reqwest::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Rewire(error) => write!(f, "{}", error),
+            Error::Reqwest(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Rewire(error) => Some(error),
+            Error::Reqwest(error) => Some(error),
+        }
+    }
+}
+
+impl From<RewireError> for Error {
+    fn from(error: RewireError) -> Self {
+        Error::Rewire(error)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Reqwest(error)
+    }
+}
+
+/// Errors raised while loading a [`RewireConfig`](crate::RewireConfig) from a file.
+#[cfg(feature = "config")]
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file could not be read.
+    Io {
+        path: std::path::PathBuf,
+        error: std::io::Error,
+    },
+    /// The file extension is not one of `toml`, `json`, `yaml` or `yml`.
+    UnsupportedFormat { path: std::path::PathBuf },
+    /// The file content is not a valid configuration. `line` and `column` start at 1, and are
+    /// `None` when the parser cannot locate the error.
+    Parse {
+        path: Option<std::path::PathBuf>,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+    /// The configuration was parsed, but one of its rules is invalid.
+    Rule(RewireError),
+}
+
+#[cfg(feature = "config")]
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, error } => {
+                write!(f, "cannot read `{}`: {}", path.display(), error)
+            }
+            ConfigError::UnsupportedFormat { path } => {
+                write!(
+                    f,
+                    "unsupported configuration format for `{}`",
+                    path.display()
+                )
+            }
+            ConfigError::Parse {
+                path,
+                line,
+                column,
+                message,
+            } => {
+                if let Some(path) = path {
+                    write!(f, "{}:", path.display())?;
+                }
+                if let Some(line) = line {
+                    write!(f, "{}:", line)?;
+                }
+                if let Some(column) = column {
+                    write!(f, "{}:", column)?;
+                }
+                if path.is_some() || line.is_some() {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", message)
+            }
+            ConfigError::Rule(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io { error, .. } => Some(error),
+            ConfigError::Rule(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<RewireError> for ConfigError {
+    fn from(error: RewireError) -> Self {
+        ConfigError::Rule(error)
+    }
+}
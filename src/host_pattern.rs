@@ -0,0 +1,103 @@
+use crate::RewireError;
+use reqwest::Url;
+use std::net::IpAddr;
+use url::Host;
+
+/// A pattern matching the host of a request, as given to
+/// [`allow_host`](crate::RewireClientBuilder::allow_host) or
+/// [`deny_host`](crate::RewireClientBuilder::deny_host).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum HostPattern {
+    /// A domain, compared case-insensitively, e.g. `localhost`.
+    Domain(String),
+    /// Any subdomain of a domain, e.g. `*.prod.example.com`.
+    Subdomains(String),
+    /// An IP address, e.g. `127.0.0.1` or `::1`.
+    Ip(IpAddr),
+    /// An IP network in CIDR notation, e.g. `127.0.0.0/8`.
+    Network { address: IpAddr, prefix: u8 },
+}
+
+impl HostPattern {
+    pub(crate) fn parse(pattern: &str) -> Result<Self, RewireError> {
+        let invalid = || RewireError::InvalidHostPattern {
+            pattern: pattern.to_string(),
+        };
+        let trimmed = pattern.trim();
+        if let Some((address, prefix)) = trimmed.split_once('/') {
+            let address: IpAddr = address.parse().map_err(|_| invalid())?;
+            let prefix: u8 = prefix.parse().map_err(|_| invalid())?;
+            let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+            if prefix > max_prefix {
+                return Err(invalid());
+            }
+            return Ok(Self::Network { address, prefix });
+        }
+        let unbracketed = trimmed
+            .strip_prefix('[')
+            .and_then(|host| host.strip_suffix(']'))
+            .unwrap_or(trimmed);
+        if let Ok(address) = unbracketed.parse() {
+            return Ok(Self::Ip(address));
+        }
+        let (wildcard, domain) = match trimmed.strip_prefix("*.") {
+            Some(domain) => (true, domain),
+            None => (false, trimmed),
+        };
+        if domain.is_empty() || domain.contains(['*', '/', ':', '[', ']']) {
+            return Err(invalid());
+        }
+        let domain = domain.to_ascii_lowercase();
+        Ok(if wildcard {
+            Self::Subdomains(domain)
+        } else {
+            Self::Domain(domain)
+        })
+    }
+
+    /// Whether the host of `url` matches this pattern. URLs without a host never match.
+    pub(crate) fn matches(&self, url: &Url) -> bool {
+        let host = match url.host() {
+            Some(Host::Domain(domain)) => return self.matches_domain(domain),
+            Some(Host::Ipv4(address)) => IpAddr::V4(address),
+            Some(Host::Ipv6(address)) => IpAddr::V6(address),
+            None => return false,
+        };
+        match self {
+            Self::Ip(address) => *address == host,
+            Self::Network { address, prefix } => in_network(host, *address, *prefix),
+            Self::Domain(_) | Self::Subdomains(_) => false,
+        }
+    }
+
+    fn matches_domain(&self, domain: &str) -> bool {
+        match self {
+            Self::Domain(pattern) => domain.eq_ignore_ascii_case(pattern),
+            Self::Subdomains(pattern) => {
+                domain
+                    .len()
+                    .checked_sub(pattern.len() + 1)
+                    .is_some_and(|dot| {
+                        domain.as_bytes()[dot] == b'.'
+                            && domain[dot + 1..].eq_ignore_ascii_case(pattern)
+                    })
+            }
+            Self::Ip(_) | Self::Network { .. } => false,
+        }
+    }
+}
+
+/// Whether `host` belongs to the network of `address` with a `prefix`-bit mask.
+fn in_network(host: IpAddr, address: IpAddr, prefix: u8) -> bool {
+    match (host, address) {
+        (IpAddr::V4(host), IpAddr::V4(address)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            u32::from(host) & mask == u32::from(address) & mask
+        }
+        (IpAddr::V6(host), IpAddr::V6(address)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            u128::from(host) & mask == u128::from(address) & mask
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,225 @@
+use crate::host_pattern::HostPattern;
+use crate::{RewireError, RewireRules};
+use reqwest::{Method, Url};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+/// What a rewiring client did with one request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    /// The method of the request.
+    pub method: Method,
+    /// The URL the request was made to.
+    pub original_url: Url,
+    /// The URL the request was sent to, or `None` if it was sent unchanged or refused.
+    pub rewired_url: Option<Url>,
+    /// The rule that rewired the request. Rules have no separate id: a rule is identified by its
+    /// source as it was registered, prefixed with its methods and followed by its headers if it is
+    /// restricted to some (e.g. `POST https://api.com/payments`), or by its pattern for a regex
+    /// rule. Unused rule reports and [`Journal::assert_all_rules_used`] list rules the same way.
+    pub rule: Option<String>,
+    /// The reason the request was not sent, if it was refused.
+    pub error: Option<RewireError>,
+    /// When the request was rewired.
+    pub timestamp: SystemTime,
+}
+
+impl JournalEntry {
+    /// The URL the request was sent to, or `None` if it was refused.
+    pub fn destination(&self) -> Option<&Url> {
+        match self.error {
+            Some(_) => None,
+            None => Some(self.rewired_url.as_ref().unwrap_or(&self.original_url)),
+        }
+    }
+}
+
+impl fmt::Display for JournalEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.original_url)?;
+        match (&self.rewired_url, &self.rule, &self.error) {
+            (_, _, Some(error)) => write!(f, " (refused: {})", error),
+            (Some(rewired), Some(rule), None) => write!(f, " -> {} (rule `{}`)", rewired, rule),
+            _ => write!(f, " (sent unchanged)"),
+        }
+    }
+}
+
+/// The number of times a request is expected, built with [`times`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Times(usize);
+
+/// Expects a request exactly `n` times, e.g. in [`Journal::assert_rewired`].
+pub fn times(n: usize) -> Times {
+    Times(n)
+}
+
+impl fmt::Display for Times {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            1 => write!(f, "once"),
+            n => write!(f, "{} times", n),
+        }
+    }
+}
+
+/// An in-memory record of every request handled by a rewiring client, enabled with
+/// [`journal`](crate::RewireClientBuilder::journal).
+///
+/// Cloning a journal is cheap and shares the same entries, so the journal of a client also
+/// records the requests of its clones.
+///
+/// # Examples
+///
+/// ```
+/// use reqwest_rewire::RewireClient;
+///
+/// let client = RewireClient::builder()
+///     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+///     .journal(true)
+///     .build()
+///     .unwrap();
+/// let journal = client.journal().unwrap();
+/// assert!(journal.is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Journal {
+    entries: Arc<Mutex<Vec<JournalEntry>>>,
+}
+
+impl Journal {
+    /// Returns a copy of the entries recorded so far, oldest first.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.lock().clone()
+    }
+
+    /// The number of entries recorded so far.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no request was recorded since the journal was created or last cleared.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Removes every entry, e.g. between two tests sharing a client.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Asserts that requests to `url` were rewired exactly `times`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the observed traffic if the count differs, or if `url` is not a valid URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reqwest_rewire::{RewireClient, times};
+    ///
+    /// let client = RewireClient::builder()
+    ///     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+    ///     .journal(true)
+    ///     .build()
+    ///     .unwrap();
+    /// client.journal().unwrap().assert_rewired("https://real-api.com/api/users", times(0));
+    /// ```
+    #[track_caller]
+    pub fn assert_rewired(&self, url: &str, times: Times) {
+        let url =
+            Url::parse(url).unwrap_or_else(|error| panic!("invalid URL `{}`: {}", url, error));
+        let entries = self.entries();
+        let is_match =
+            |entry: &JournalEntry| entry.original_url == url && entry.rewired_url.is_some();
+        let observed = Times(entries.iter().filter(|entry| is_match(entry)).count());
+        if observed != times {
+            panic!(
+                "expected `{}` to be rewired {}, but it was rewired {}\n\n{}",
+                url,
+                times,
+                observed,
+                traffic(&entries, is_match)
+            );
+        }
+    }
+
+    /// Asserts that no request was sent to a host matching `pattern`, once rewired. Patterns have
+    /// the syntax of [`allow_host`](crate::RewireClientBuilder::allow_host).
+    ///
+    /// # Panics
+    ///
+    /// Panics with the observed traffic if a request was sent to the host, or if `pattern` is
+    /// invalid.
+    #[track_caller]
+    pub fn assert_not_called(&self, pattern: &str) {
+        let host = HostPattern::parse(pattern).unwrap_or_else(|error| panic!("{}", error));
+        let entries = self.entries();
+        let is_match =
+            |entry: &JournalEntry| entry.destination().is_some_and(|url| host.matches(url));
+        let count = entries.iter().filter(|entry| is_match(entry)).count();
+        if count > 0 {
+            panic!(
+                "expected no request to `{}`, but it was called {}\n\n{}",
+                pattern,
+                Times(count),
+                traffic(&entries, is_match)
+            );
+        }
+    }
+
+    /// Asserts that every rule of `rules` rewired at least one recorded request. Overridden rules,
+    /// which cannot match any request, are not checked.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the unused rules and the observed traffic if a rule was never used.
+    #[track_caller]
+    pub fn assert_all_rules_used(&self, rules: &RewireRules) {
+        let entries = self.entries();
+        let unused: Vec<String> = rules
+            .sources()
+            .into_iter()
+            .filter(|source| {
+                !entries
+                    .iter()
+                    .any(|entry| entry.rule.as_ref() == Some(source))
+            })
+            .collect();
+        if !unused.is_empty() {
+            let unused: Vec<String> = unused
+                .iter()
+                .map(|source| format!("    {}", source))
+                .collect();
+            panic!(
+                "expected every rule to be used, but these never matched:\n{}\n\n{}",
+                unused.join("\n"),
+                traffic(&entries, |_| false)
+            );
+        }
+    }
+
+    pub(crate) fn record(&self, entry: JournalEntry) {
+        self.lock().push(entry);
+    }
+
+    // Entries are pushed whole, so a poisoned journal is still consistent.
+    fn lock(&self) -> MutexGuard<'_, Vec<JournalEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Lists `entries` one per line for assertion failures, marking those matching `is_match`.
+fn traffic(entries: &[JournalEntry], is_match: impl Fn(&JournalEntry) -> bool) -> String {
+    if entries.is_empty() {
+        return "observed traffic: none".to_string();
+    }
+    let mut traffic = "observed traffic:".to_string();
+    for entry in entries {
+        let marker = if is_match(entry) { '>' } else { ' ' };
+        traffic.push_str(&format!("\n  {} {}", marker, entry));
+    }
+    traffic
+}
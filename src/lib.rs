@@ -34,21 +34,28 @@
 //!
 //! Your application code depends on a trait, not a concrete client:
 //!
-//! ```rust
-//! use reqwest_rewire::TestableClient;
+//! ```rust,no_run
+//! use reqwest_rewire::{TestableClient, TestableClientExt};
 //!
-//! fn fetch_data(client: Box<dyn TestableClient>) {
+//! async fn fetch_data(client: &dyn TestableClient) {
 //!     client
 //!         .get("https://real-api.com/api/users")
 //!         .send()
+//!         .await
 //!         .unwrap();
 //! }
 //! ```
 //!
+//! The request methods live in the `TestableClientExt` extension trait. They accept any
+//! `reqwest::IntoUrl` (`&str`, `String`, `reqwest::Url`...), like those of `reqwest::Client`.
+//! Already built `reqwest::Request`s can be sent with `execute`, which rewires them too. Requests
+//! built with `build()` come out rewired, so they can be handed to any client, and `execute` sends
+//! them without rewiring them a second time.
+//!
 //! You can also use the Client enum, which has two states:
 //!
 //!
-//! ```rust
+//! ```rust,ignore
 //! enum Client {
 //!    ReqwestClient(reqwest::Client),
 //!    TestClient(RewireClient),
@@ -56,29 +63,37 @@
 //! ```
 //! It does implement the `TestableClient` trait, but it allows you to use it instead of dynamic object:
 //!
-//! ```rust
-//!     use reqwest_rewire;
-
-//!     fn fetch_data(client: reqwest_rewire::Client) { // No Box<dyn ...>
-//!         client
-//!             .get("https://real-api.com/api/users")
-//!             .send()
-//!             .unwrap();
-//!     }
+//! ```rust,no_run
+//! use reqwest_rewire::TestableClientExt;
+//!
+//! async fn fetch_data(client: reqwest_rewire::Client) { // No Box<dyn ...>
+//!     client
+//!         .get("https://real-api.com/api/users")
+//!         .send()
+//!         .await
+//!         .unwrap();
+//! }
 //! ```
 //!
 //!
 //! ### 2. Use `reqwest::Client` in production
 //!
-//! ```rust
+//! ```rust,no_run
+//! # use reqwest_rewire::TestableClient;
+//! # async fn fetch_data(client: &dyn TestableClient) {}
+//! # async fn run() {
 //! let client = reqwest::Client::new();
-//! fetch_data(&client);
+//! fetch_data(&client).await;
+//! # }
 //! ```
 //!
 //! ### 3. Use `RewireClient` in tests
-//! ```rust
+//! ```rust,no_run
 //! use std::collections::HashMap;
 //! use reqwest_rewire::RewireClient;
+//! # use reqwest_rewire::TestableClient;
+//! # async fn fetch_data(client: &dyn TestableClient) {}
+//! # async fn run() {
 //!
 //! let mut redirects = HashMap::new();
 //! redirects.insert(
@@ -88,21 +103,169 @@
 //!
 //! let client = RewireClient::new(redirects);
 //!
-//! fetch_data(&client);
+//! fetch_data(&client).await;
+//! # }
 //! ```
 //! No application code changes required 🎉
 //!
+//! `RewireClient::new` ignores invalid rules. Use `RewireClient::try_new` to get a `RewireError` instead,
+//! so a misconfigured fixture fails at setup rather than sending requests to the real host.
+//!
+//! Rules can also be registered fluently with `RewireClient::builder()`, mirroring `reqwest::ClientBuilder`:
+//!
+//! ```rust
+//! use reqwest_rewire::RewireClient;
+//!
+//! let client = RewireClient::builder()
+//!     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+//!     .with_reqwest_client(reqwest::Client::new())
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! Rules can be added and removed after the client has been injected. Clones share the same rules,
+//! so they all see the change from their next request on:
+//!
+//! ```rust
+//! let client = reqwest_rewire::RewireClient::builder().build().unwrap();
+//! let injected = reqwest_rewire::Client::TestClient(client.clone());
+//!
+//! client.add_rule("https://real-api.com/api/", "http://localhost:3000/api-mock/").unwrap();
+//! client.remove_rule("https://real-api.com/api/");
+//! client.clear();
+//! ```
+//!
+//! `override_rule` rewires an endpoint temporarily, e.g. to a failure-injecting mock, and restores the
+//! previous rule when the returned guard is dropped.
+//!
+//! By default, requests matching no rule are sent to their original URL, so a forgotten rule silently
+//! hits the real API. `UnmatchedPolicy::Error` makes them fail with `RewireError::Unmatched` instead,
+//! as an `Error::Rewire` error without touching the network, and `UnmatchedPolicy::Panic` fails the
+//! test right away. Hosts given to `allow_host` are always let through:
+//!
+//! ```rust
+//! use reqwest_rewire::{RewireClient, UnmatchedPolicy};
+//!
+//! let client = RewireClient::builder()
+//!     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+//!     .unmatched(UnmatchedPolicy::Error)
+//!     .allow_host("localhost")
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! Host patterns can be domains, wildcard subdomains or CIDR networks, and `deny_host` blocks every
+//! request whose destination, once rewired, matches a pattern, with a `RewireError::Blocked` error.
+//! Denied hosts take precedence over allowed ones.
+//!
+//! To find out what a client actually did, e.g. in a flaky test, `journal(true)` records the method,
+//! original URL, rewritten URL, matched rule and time of every request, refused ones included. The
+//! journal is shared by the clones of the client, and can be cleared between tests:
+//!
+//! ```rust
+//! let client = reqwest_rewire::RewireClient::builder()
+//!     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+//!     .journal(true)
+//!     .build()
+//!     .unwrap();
+//!
+//! for entry in client.journal().unwrap().entries() {
+//!     println!("{} {} -> {:?}", entry.method, entry.original_url, entry.rewired_url);
+//! }
+//! client.journal().unwrap().clear();
+//! ```
+//!
+//! The journal also backs `assert_rewired`, `assert_not_called` and `assert_all_rules_used`, which
+//! panic with the requests actually observed when they fail.
+//!
+//! Rules of long-lived fixtures tend to outlive the endpoints they mock. With
+//! `unused_rules(UnusedRulePolicy::Warn)` or `UnusedRulePolicy::Panic`, a client lists the rules that
+//! never rewrote a request once its last clone is dropped.
+//!
+//! In CI or docker-compose setups, rules can come from the environment instead, so the same binary
+//! talks to local stand-ins without recompiling. `Client::from_env()` reads `REQWEST_REWIRE_RULES`
+//! (rules separated by commas or newlines) and `REQWEST_REWIRE_0`, `REQWEST_REWIRE_1`..., and returns
+//! a plain `Client::ReqwestClient` when none are set:
+//!
+//! ```sh
+//! REQWEST_REWIRE_RULES="https://real-api.com/api/->http://mock:3000/api-mock/"
+//! ```
+//!
+//! ```rust
+//! let client = reqwest_rewire::Client::from_env().unwrap();
+//! ```
+//!
+//! `RewireClient::from_env(prefix)` reads the same variables with a custom prefix.
+//!
+//! ### 4. Blocking clients
+//!
+//! The same rules work with `reqwest::blocking::Client` through `BlockingRewireClient`,
+//! the `BlockingTestableClient` trait and the `BlockingClient` enum:
+//!
+//! ```rust,no_run
+//! use reqwest_rewire::{BlockingRewireClient, BlockingTestableClientExt};
+//!
+//! let client = BlockingRewireClient::builder()
+//!     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+//!     .build()
+//!     .unwrap();
+//!
+//! client.get("https://real-api.com/api/users").send().unwrap();
+//! ```
+//!
+//!
+//! ## Optional Features
+//!
+//! - `middleware`: exposes `RewireMiddleware`, a [`reqwest-middleware`](https://docs.rs/reqwest-middleware)
+//!   middleware applying the same rules to a `ClientWithMiddleware`.
+//! - `tower`: exposes `RewireLayer`, a [`tower`](https://docs.rs/tower) layer rewriting the URI and
+//!   `Host` header of `http::Request`s, for clients built on `hyper` or `tower`. A layer can share
+//!   the rules of an existing client through `RewireRules`.
+//! - `config`: exposes `RewireConfig`, an ordered list of rules read from TOML, JSON or YAML files,
+//!   and `RewireClient::from_config_file`. Parse errors report the file, line and column.
+//! - `json` and `multipart`: enable the reqwest features of the same name, and add `json` and
+//!   `multipart` to `RewireRequestBuilder` and `BlockingRewireRequestBuilder`.
+//!
+//! - `regex`: adds regex rules with `rewire_regex` or `Rule::regex`, whose captures can be used in
+//!   the target URL, e.g. to move a version segment into the query. Regex rules are tried before
+//!   prefix rules, in the order they were added, and the first match wins.
+//!
 //!
 //! ## Rewrite Behavior
 //!
 //! - URLs are parsed using `url::Url`
 //! - Rewrites match on:
 //! - scheme
-//! - host
-//! - **path prefix**
-//! - The rule with the **longest matching path** is selected
-//! - Query strings and fragments are preserved
+//! - host (a `*` label in the source matches any single label, e.g. `https://*.api.example.com/`)
+//! - port (default ports are normalized, so `http://api/` and `http://api:80/` are the same)
+//! - **path prefix** (whole path segments; `{name}` matches any single segment and a trailing
+//!   `{name*}` every remaining one, e.g. `https://api.com/users/{id}`)
+//! - method, for rules added with `rewire_methods` (e.g. only `POST https://api.com/payments`)
+//! - headers, for rules added with `rewire_headers` (e.g. only `Accept: application/vnd.v2+json`)
+//! - The rule with the **most literal path segments** matched is selected, then the one with the
+//!   **longest matching path**
+//! - Among the rules of the same source, an active override comes first, then rules restricted to
+//!   the request method, then rules restricted to its headers, those requiring the most headers
+//!   first, then the rule for any request. Between header rules requiring as many headers, the
+//!   one whose headers sorted by name then value come first wins, whatever the order rules were
+//!   added in
+//! - Exact hosts take precedence over wildcard hosts, whatever their paths, and wildcard hosts with more
+//!   literal labels over those with fewer
+//! - `{1}`, `{2}`... in a target are replaced with the labels matched by the `*`s of the source, from
+//!   left to right, and `{name}` or `{name*}` with the path segments they matched. Braces in the
+//!   target of a source capturing nothing are kept as they are. A rule does not match a request
+//!   if its filled target is not a valid URL, or has an empty path segment left by an empty capture
+//! - Rules are parsed once when they are added, and lookup cost does not grow with the number of
+//!   exact-host rules
+//! - The path following the matched prefix is appended to the target path, whatever their depths,
+//!   unless a `{name*}` segment captured it
+//! - Trailing slashes on rules are not significant, and a trailing slash on the request is kept
+//! - Query strings, fragments, credentials and percent-encoded path segments are preserved
 //! - Invalid URLs fall back to the original request
+//! - With the `regex` feature, regex rules are tried first, and prefix rules only apply to requests
+//!   matching none of them. Regex rules match on the URL only, so they also come before rules
+//!   restricted to methods or headers, but not before an active override the request would use
+//! - Requests matching no rule are sent unchanged, unless the `UnmatchedPolicy` refuses them
 //!
 //! ### Example
 //!
@@ -121,7 +284,6 @@
 //!
 //! - HTTP mocking or response stubbing
 //! - Network interception or proxying
-//! - Wildcard URLs (`*` is not valid in URLs)
 //!
 //! For full HTTP mocking, consider tools like `wiremock`, `mockito`, or `httpmock`.
 //!
@@ -158,9 +320,63 @@
 //! Issues, ideas, and pull requests are welcome!
 //! Please open an issue before making large changes.
 
+#[cfg(feature = "regex")]
+mod regex_rule;
 mod rewire_client;
-pub use rewire_client::RewireClient;
+mod rewire_rules;
+mod rewired_requests;
+mod rule_table;
+mod target_template;
+pub use rewire_client::{GenericRewireClient, RewireClient};
+pub use rewire_rules::RewireRules;
+mod rule;
+pub use rule::Rule;
+mod rule_guard;
+pub use rule_guard::RuleGuard;
+mod rewire_client_builder;
+#[cfg(feature = "middleware")]
+pub use rewire_client_builder::RewireMiddlewareBuilder;
+pub use rewire_client_builder::{BlockingRewireClientBuilder, RewireClientBuilder};
+mod rewire_rules_builder;
+pub use rewire_rules_builder::RewireRulesBuilder;
 mod testable_client;
-pub use testable_client::TestableClient;
+pub use testable_client::{TestableClient, TestableClientExt};
+mod rewire_request_builder;
+pub use rewire_request_builder::RewireRequestBuilder;
 mod client;
 pub use client::Client;
+mod unmatched_policy;
+pub use unmatched_policy::UnmatchedPolicy;
+mod unused_rule_policy;
+pub use unused_rule_policy::UnusedRulePolicy;
+mod journal;
+mod unused_rule_report;
+pub use journal::{Journal, JournalEntry, Times, times};
+mod error;
+mod host_pattern;
+mod request_policy;
+pub use error::{Error, RewireError};
+mod env_rules;
+pub use env_rules::DEFAULT_ENV_PREFIX;
+#[cfg(feature = "config")]
+pub use error::ConfigError;
+mod blocking_rewire_client;
+pub use blocking_rewire_client::BlockingRewireClient;
+mod blocking_testable_client;
+pub use blocking_testable_client::{BlockingTestableClient, BlockingTestableClientExt};
+mod blocking_rewire_request_builder;
+pub use blocking_rewire_request_builder::BlockingRewireRequestBuilder;
+mod blocking_client;
+pub use blocking_client::BlockingClient;
+#[cfg(feature = "middleware")]
+mod rewire_middleware;
+#[cfg(feature = "middleware")]
+pub use rewire_middleware::RewireMiddleware;
+#[cfg(feature = "tower")]
+mod rewire_layer;
+#[cfg(feature = "tower")]
+pub use rewire_layer::{RewireLayer, RewireService};
+#[cfg(feature = "config")]
+mod rewire_config;
+#[cfg(feature = "config")]
+pub use rewire_config::{ConfigFormat, RewireConfig, RuleConfig, RuleOptions};
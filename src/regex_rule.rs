@@ -0,0 +1,63 @@
+use crate::RewireError;
+use crate::rule_table::Rewrite;
+use regex::Regex;
+use reqwest::Url;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A rule rewriting the URLs matched by a regular expression into a replacement URL.
+pub(crate) struct RegexRule {
+    pub(crate) pattern: String,
+    /// `pattern`, anchored at both ends.
+    regex: Regex,
+    replacement: String,
+    pub(crate) hits: AtomicU64,
+}
+
+impl RegexRule {
+    pub(crate) fn new(pattern: &str, replacement: &str) -> Result<Self, RewireError> {
+        let regex = Regex::new(&format!("^(?:{})$", pattern)).map_err(|error| {
+            RewireError::InvalidRegex {
+                pattern: pattern.to_string(),
+                message: error.to_string(),
+            }
+        })?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            regex,
+            replacement: replacement.to_string(),
+            hits: AtomicU64::new(0),
+        })
+    }
+
+    /// Rewrites `url` if the pattern matches it whole, query and fragment excluded.
+    ///
+    /// The captures are expanded into the replacement with the `$1` and `${name}` syntax of
+    /// [`regex::Captures::expand`]. The request query is appended to that of the replacement, and
+    /// the request fragment replaces it. Returns `None` if the pattern does not match, or if the
+    /// expanded replacement is not a valid URL.
+    pub(crate) fn rewrite(&self, url: &Url) -> Option<Rewrite> {
+        let mut matched = url.clone();
+        matched.set_query(None);
+        matched.set_fragment(None);
+        let captures = self.regex.captures(matched.as_str())?;
+        let mut expanded = String::new();
+        captures.expand(&self.replacement, &mut expanded);
+        let mut rewired = Url::parse(&expanded).ok()?;
+
+        if let Some(query) = url.query() {
+            let query = match rewired.query() {
+                Some(target) if !target.is_empty() => format!("{}&{}", target, query),
+                _ => query.to_string(),
+            };
+            rewired.set_query(Some(&query));
+        }
+        if let Some(fragment) = url.fragment() {
+            rewired.set_fragment(Some(fragment));
+        }
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(Rewrite {
+            url: rewired,
+            rule: self.pattern.clone(),
+        })
+    }
+}
@@ -0,0 +1,118 @@
+use crate::host_pattern::HostPattern;
+use crate::rule_table::{RequestHead, Rewrite};
+use crate::{Journal, JournalEntry, RewireError, RewireRules, UnmatchedPolicy};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Url};
+use std::time::SystemTime;
+
+/// The checks applied to a request besides its rewrite rules.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RequestPolicy {
+    unmatched: UnmatchedPolicy,
+    allowed_hosts: Vec<HostPattern>,
+    denied_hosts: Vec<(String, HostPattern)>,
+}
+
+impl RequestPolicy {
+    /// Parses the host patterns of a policy, failing on the first invalid one.
+    pub(crate) fn new(
+        unmatched: UnmatchedPolicy,
+        allowed_hosts: &[String],
+        denied_hosts: &[String],
+    ) -> Result<Self, RewireError> {
+        Ok(Self {
+            unmatched,
+            allowed_hosts: allowed_hosts
+                .iter()
+                .map(|pattern| HostPattern::parse(pattern))
+                .collect::<Result<_, _>>()?,
+            denied_hosts: denied_hosts
+                .iter()
+                .map(|pattern| Ok((pattern.clone(), HostPattern::parse(pattern)?)))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Returns the URL a `method` request to `url` carrying `headers` must be sent to, `None` to
+    /// send it unchanged, or the reason it must not be sent, and records the decision in `journal`
+    /// if any.
+    ///
+    /// A request whose destination, once rewired, is a denied host is blocked. Otherwise, a request
+    /// matching no rule is let through if its host is allowed, and handled by the unmatched policy
+    /// if not.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a request matches no rule and the unmatched policy is
+    /// [`UnmatchedPolicy::Panic`]. Requests to a denied host fail with an error instead.
+    pub(crate) fn resolve(
+        &self,
+        rules: &RewireRules,
+        journal: Option<&Journal>,
+        method: &Method,
+        url: &Url,
+        headers: &HeaderMap,
+    ) -> Result<Option<Url>, RewireError> {
+        let head = RequestHead {
+            method: Some(method),
+            headers: Some(headers),
+        };
+        let decision = self.decide(rules, head, url);
+        if let Some(journal) = journal {
+            let (rewired_url, rule, error) = match &decision {
+                Ok(Some(rewrite)) => (Some(rewrite.url.clone()), Some(rewrite.rule.clone()), None),
+                Ok(None) => (None, None, None),
+                Err(error) => (None, None, Some(error.clone())),
+            };
+            journal.record(JournalEntry {
+                method: method.clone(),
+                original_url: url.clone(),
+                rewired_url,
+                rule,
+                error,
+                timestamp: SystemTime::now(),
+            });
+        }
+        match decision {
+            Ok(rewrite) => Ok(rewrite.map(|rewrite| rewrite.url)),
+            Err(error @ RewireError::Unmatched { .. })
+                if self.unmatched == UnmatchedPolicy::Panic =>
+            {
+                panic!("{}", error)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    fn decide(
+        &self,
+        rules: &RewireRules,
+        head: RequestHead,
+        url: &Url,
+    ) -> Result<Option<Rewrite>, RewireError> {
+        let rewired = rules.resolve(head, url);
+        let destination = rewired.as_ref().map_or(url, |rewrite| &rewrite.url);
+        if let Some((pattern, _)) = self
+            .denied_hosts
+            .iter()
+            .find(|(_, denied)| denied.matches(destination))
+        {
+            return Err(RewireError::Blocked {
+                url: destination.to_string(),
+                pattern: pattern.clone(),
+            });
+        }
+        if rewired.is_some()
+            || self.unmatched == UnmatchedPolicy::Allow
+            || self
+                .allowed_hosts
+                .iter()
+                .any(|allowed| allowed.matches(url))
+        {
+            return Ok(rewired);
+        }
+        Err(RewireError::Unmatched {
+            url: url.to_string(),
+        })
+    }
+}
@@ -1,236 +1,307 @@
-use crate::TestableClient;
-use http::Method;
-use reqwest::{RequestBuilder, Url};
-use std::borrow::Cow;
+use crate::env_rules::env_rules;
+use crate::request_policy::RequestPolicy;
+use crate::rewired_requests::RewiredRequests;
+use crate::unused_rule_report::UnusedRuleReport;
+use crate::{
+    Journal, RewireClientBuilder, RewireError, RewireRules, RuleGuard, TestableClient, Times,
+};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Request, Url};
 use std::collections::HashMap;
+use std::sync::Arc;
 
-pub struct RewireClient {
-    redirects: HashMap<String, String>,
-    client: reqwest::Client,
+/// A client rewiring the requests it sends through the reqwest client `C`, used as a
+/// [`RewireClient`] or a [`BlockingRewireClient`](crate::BlockingRewireClient).
+///
+/// Clones share the same rules and journal.
+#[derive(Clone)]
+pub struct GenericRewireClient<C> {
+    pub(crate) rules: RewireRules,
+    pub(crate) client: C,
+    pub(crate) policy: RequestPolicy,
+    pub(crate) journal: Option<Journal>,
+    pub(crate) rewired: RewiredRequests,
+    /// Only held to report unused rules when the last clone is dropped.
+    pub(crate) _unused_rule_report: Option<Arc<UnusedRuleReport>>,
 }
 
+/// A client rewiring the requests it sends through a `reqwest::Client`.
+pub type RewireClient = GenericRewireClient<reqwest::Client>;
+
 impl RewireClient {
+    /// Creates a client from `redirects`, ignoring invalid rules.
     pub fn new(redirects: HashMap<String, String>) -> Self {
-        Self {
-            client: reqwest::Client::new(),
-            redirects,
-        }
-    }
-
-    pub fn from_reqwest_client(
-        client: reqwest::Client,
-        redirects: HashMap<String, String>,
-    ) -> Self {
-        Self { client, redirects }
+        Self::builder()
+            .rewires(redirects)
+            .strict(false)
+            .build()
+            .expect("non-strict builds never fail")
     }
-}
 
-impl RewireClient {
-    /// Gets the rewired URL from the request-rewire client instance's `redirects` propriety.
-    /// If the request URL is not in the `redirects` hashmap, the request URL is unchanged.
-    ///
-    /// The request methods (.get(), .post(), .put()...) of the request-rewire client all use this method internally.
+    /// Creates a client from `redirects`, failing if any rule is invalid instead of ignoring it.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::collections::HashMap;
-    /// use reqwest_rewire::rewire_client::RewireClient;
-    /// use reqwest_rewire::testable_client::TestableClient;
+    /// use reqwest_rewire::{RewireClient, RewireError};
     ///
     /// let mut redirects: HashMap<String, String> = HashMap::new();
-    /// redirects.insert("http://localhost:8080/example".into_string(), "http://localhost:8080/rewired".into_string());
-    /// let client = RewireClient::new(redirects);
-    /// client.get("http://localhost:8080/example").send().await.unwrap(); // hits http://localhost:8080/rewired
-    /// client.get("http://localhost:8080/example/nested_path").send().await.unwrap(); // hits http://localhost:8080/rewired/nested_path
-    /// client.get("http://localhost:8080/example?foo=bar").send().await.unwrap(); // hits http://localhost:8080/rewired?foo=bar
+    /// redirects.insert("http://localhost:8080/example".to_string(), "not a url".to_string());
+    /// let error = RewireClient::try_new(redirects).err().unwrap();
+    /// assert!(matches!(error, RewireError::InvalidTarget { .. }));
     /// ```
-    fn get_url<'a>(&'a self, url: &'a str) -> Cow<'a, str> {
-        let parsed = match Url::parse(url) {
-            Ok(u) => u,
-            Err(_) => return Cow::Borrowed(url),
-        };
-
-        let key = self
-            .redirects
-            .keys()
-            .flat_map(|r| Url::parse(r.as_str()))
-            .filter(|u| {
-                u.host_str().is_some()
-                    && u.host_str() == parsed.host_str()
-                    && parsed.path().starts_with(u.path())
-            })
-            .max_by(|a, b| a.path().len().cmp(&b.path().len()));
-
-        if key.is_none() {
-            return Cow::Borrowed(url);
-        }
+    pub fn try_new(redirects: HashMap<String, String>) -> Result<Self, RewireError> {
+        Self::builder().rewires(redirects).build()
+    }
 
-        let key = key.unwrap();
-        let rewire = Url::parse(self.redirects.get(key.as_str()).unwrap());
-        let rewire = match rewire {
-            Ok(r) => r,
-            Err(_) => return Cow::Borrowed(url),
-        };
-
-        let segment_count = rewire.path_segments().map(|s| s.count()).unwrap_or(0);
-        let rest_of_path = parsed
-            .path_segments()
-            .map(|segments| segments.skip(segment_count).collect::<Vec<_>>().join("/"))
-            .unwrap_or_default();
-
-        let mut rewire = rewire.to_string();
-        if !rest_of_path.is_empty() {
-            rewire.push('/');
-            rewire.push_str(&rest_of_path);
-        }
-        let query_string = parsed.query().unwrap_or_default();
-        if !query_string.is_empty() {
-            rewire.push('?');
-            rewire.push_str(query_string);
-        }
-        Cow::Owned(rewire)
+    /// Creates a client sending requests through `client`, ignoring invalid rules.
+    pub fn from_reqwest_client(
+        client: reqwest::Client,
+        redirects: HashMap<String, String>,
+    ) -> Self {
+        Self::builder()
+            .rewires(redirects)
+            .with_reqwest_client(client)
+            .strict(false)
+            .build()
+            .expect("non-strict builds never fail")
     }
-}
 
-impl TestableClient for RewireClient {
-    /// Sends a GET request to a rewired URL if it is in the client's `redirects` hashmap, or else to the URL in method call.
+    /// Creates a client from the configuration file at `path`, in TOML, JSON or YAML depending on
+    /// its extension. See [`RewireConfig`](crate::RewireConfig) for the file layout.
     ///
     /// # Examples
     ///
-    /// ```
-    /// use std::collections::HashMap;
-    /// use reqwest_rewire::rewire_client::RewireClient;
-    /// use reqwest_rewire::testable_client::TestableClient;
+    /// ```no_run
+    /// use reqwest_rewire::RewireClient;
     ///
-    /// let mut redirects: HashMap<String, String> = HashMap::new();
-    /// redirects.insert("http://localhost:8080/example".into_string(), "http://localhost:8080/rewired".into_string());
-    /// let client = RewireClient::new(redirects);
-    /// client.get("http://localhost:8080/example").send().await.unwrap(); // hits http://localhost:8080/rewired
-    /// client.get("http://localhost:8080/example/nested_path").send().await.unwrap(); // hits http://localhost:8080/rewired/nested_path
-    /// client.get("http://localhost:8080/example?foo=bar").send().await.unwrap(); // hits http://localhost:8080/rewired?foo=bar
+    /// let client = RewireClient::from_config_file("tests/rewire.toml").unwrap();
     /// ```
-    fn get(&self, url: &str) -> RequestBuilder {
-        self.client.get(self.get_url(url).as_ref())
+    #[cfg(feature = "config")]
+    pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self, crate::ConfigError> {
+        let config = crate::RewireConfig::from_file(path)?;
+        Ok(Self::builder().config(config).build()?)
     }
 
-    /// Sends a POST request to a rewired URL if it is in the client's `redirects` hashmap, or else to the URL in method call.
+    /// Creates a client from the `source->target` rules of the `{prefix}_RULES` environment variable,
+    /// separated by commas or newlines, then of `{prefix}_0`, `{prefix}_1`... in numeric order.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
-    /// use reqwest_rewire::rewire_client::RewireClient;
-    /// use reqwest_rewire::testable_client::TestableClient;
+    /// use reqwest_rewire::RewireClient;
     ///
-    /// let mut redirects: HashMap<String, String> = HashMap::new();
-    /// redirects.insert("http://localhost:8080/example".into_string(), "http://localhost:8080/rewired".into_string());
-    /// let client = RewireClient::new(redirects);
-    /// client.post("http://localhost:8080/example").send().await.unwrap(); // hits http://localhost:8080/rewired
-    /// client.post("http://localhost:8080/example/nested_path").send().await.unwrap(); // hits http://localhost:8080/rewired/nested_path
-    /// client.post("http://localhost:8080/example?foo=bar").send().await.unwrap(); // hits http://localhost:8080/rewired?foo=bar
+    /// // REWIRE_RULES="https://api.com/->http://mock:3000/"
+    /// // REWIRE_0="https://auth.api.com/ -> http://mock:3001/"
+    /// let client = RewireClient::from_env("REWIRE").unwrap();
     /// ```
-    fn post(&self, url: &str) -> RequestBuilder {
-        self.client.post(self.get_url(url).as_ref())
+    pub fn from_env(prefix: &str) -> Result<Self, RewireError> {
+        Self::builder().rewires(env_rules(prefix)?).build()
     }
 
-    /// Sends a PUT request to a rewired URL if it is in the client's `redirects` hashmap, or else to the URL in method call.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use std::collections::HashMap;
-    /// use reqwest_rewire::rewire_client::RewireClient;
-    /// use reqwest_rewire::testable_client::TestableClient;
-    ///
-    /// let mut redirects: HashMap<String, String> = HashMap::new();
-    /// redirects.insert("http://localhost:8080/example".into_string(), "http://localhost:8080/rewired".into_string());
-    /// let client = RewireClient::new(redirects);
-    /// client.put("http://localhost:8080/example").send().await.unwrap(); // hits http://localhost:8080/rewired
-    /// client.put("http://localhost:8080/example/nested_path").send().await.unwrap(); // hits http://localhost:8080/rewired/nested_path
-    /// client.put("http://localhost:8080/example?foo=bar").send().await.unwrap(); // hits http://localhost:8080/rewired?foo=bar
-    /// ```
-    fn put(&self, url: &str) -> RequestBuilder {
-        self.client.put(self.get_url(url).as_ref())
+    /// Creates a [`RewireClientBuilder`] to configure a client.
+    pub fn builder() -> RewireClientBuilder {
+        RewireClientBuilder::new()
     }
+}
 
-    /// Sends a PATCH request to a rewired URL if it is in the client's `redirects` hashmap, or else to the URL in method call.
-    ///
-    /// # Examples
+impl<C> GenericRewireClient<C> {
+    /// The rules of this client, which can be cloned to apply them elsewhere, e.g. to a
+    /// [`RewireLayer`](crate::RewireLayer).
+    pub fn rules(&self) -> &RewireRules {
+        &self.rules
+    }
+
+    /// The journal of the requests sent by this client and its clones, or `None` unless it was
+    /// enabled with [`journal`](RewireClientBuilder::journal).
+    pub fn journal(&self) -> Option<&Journal> {
+        self.journal.as_ref()
+    }
+
+    /// Asserts that requests to `url` were rewired exactly `times`. See
+    /// [`Journal::assert_rewired`].
     ///
-    /// ```
-    /// use std::collections::HashMap;
-    /// use reqwest_rewire::rewire_client::RewireClient;
-    /// use reqwest_rewire::testable_client::TestableClient;
+    /// # Panics
     ///
-    /// let mut redirects: HashMap<String, String> = HashMap::new();
-    /// redirects.insert("http://localhost:8080/example".into_string(), "http://localhost:8080/rewired".into_string());
-    /// let client = RewireClient::new(redirects);
-    /// client.patch("http://localhost:8080/example").send().await.unwrap(); // hits http://localhost:8080/rewired
-    /// client.patch("http://localhost:8080/example/nested_path").send().await.unwrap(); // hits http://localhost:8080/rewired/nested_path
-    /// client.patch("http://localhost:8080/example?foo=bar").send().await.unwrap(); // hits http://localhost:8080/rewired?foo=bar
-    /// ```
-    fn patch(&self, url: &str) -> RequestBuilder {
-        self.client.patch(self.get_url(url).as_ref())
+    /// Panics if the assertion fails or the journal is disabled.
+    #[track_caller]
+    pub fn assert_rewired(&self, url: &str, times: Times) {
+        self.expect_journal().assert_rewired(url, times);
     }
 
-    /// Sends a DELETE request to a rewired URL if it is in the client's `redirects` hashmap, or else to the URL in method call.
+    /// Asserts that no request was sent to a host matching `pattern`. See
+    /// [`Journal::assert_not_called`].
     ///
-    /// # Examples
+    /// # Panics
     ///
-    /// ```
-    /// use std::collections::HashMap;
-    /// use reqwest_rewire::rewire_client::RewireClient;
-    /// use reqwest_rewire::testable_client::TestableClient;
-    ///
-    /// let mut redirects: HashMap<String, String> = HashMap::new();
-    /// redirects.insert("http://localhost:8080/example".into_string(), "http://localhost:8080/rewired".into_string());
-    /// let client = RewireClient::new(redirects);
-    /// client.delete("http://localhost:8080/example").send().await.unwrap(); // hits http://localhost:8080/rewired
-    /// client.delete("http://localhost:8080/example/nested_path").send().await.unwrap(); // hits http://localhost:8080/rewired/nested_path
-    /// client.delete("http://localhost:8080/example?foo=bar").send().await.unwrap(); // hits http://localhost:8080/rewired?foo=bar
-    /// ```
-    fn delete(&self, url: &str) -> RequestBuilder {
-        self.client.delete(self.get_url(url).as_ref())
+    /// Panics if the assertion fails or the journal is disabled.
+    #[track_caller]
+    pub fn assert_not_called(&self, pattern: &str) {
+        self.expect_journal().assert_not_called(pattern);
     }
 
-    /// Sends a HEAD request to a rewired URL if it is in the client's `redirects` hashmap, or else to the URL in method call.
-    ///
-    /// # Examples
+    /// Asserts that every rule of this client rewired at least one request. See
+    /// [`Journal::assert_all_rules_used`].
     ///
-    /// ```
-    /// use std::collections::HashMap;
-    /// use reqwest_rewire::rewire_client::RewireClient;
-    /// use reqwest_rewire::testable_client::TestableClient;
+    /// # Panics
     ///
-    /// let mut redirects: HashMap<String, String> = HashMap::new();
-    /// redirects.insert("http://localhost:8080/example".into_string(), "http://localhost:8080/rewired".into_string());
-    /// let client = RewireClient::new(redirects);
-    /// client.head("http://localhost:8080/example").send().await.unwrap(); // hits http://localhost:8080/rewired
-    /// client.head("http://localhost:8080/example/nested_path").send().await.unwrap(); // hits http://localhost:8080/rewired/nested_path
-    /// client.head("http://localhost:8080/example?foo=bar").send().await.unwrap(); // hits http://localhost:8080/rewired?foo=bar
-    /// ```
-    fn head(&self, url: &str) -> RequestBuilder {
-        self.client.head(self.get_url(url).as_ref())
+    /// Panics if the assertion fails or the journal is disabled.
+    #[track_caller]
+    pub fn assert_all_rules_used(&self) {
+        self.expect_journal().assert_all_rules_used(&self.rules);
+    }
+
+    #[track_caller]
+    fn expect_journal(&self) -> &Journal {
+        self.journal
+            .as_ref()
+            .expect("the journal is disabled, enable it with `journal(true)` to assert on requests")
+    }
+
+    /// Adds a `from -> to` rule, replacing any rule matching exactly the same requests.
+    /// Clones of this client share its rules, so they are rewired too.
+    pub fn add_rule(&self, from: &str, to: &str) -> Result<(), RewireError> {
+        self.rules.add_rule(from, to)
+    }
+
+    /// Removes the rule matching exactly the same requests as `from`, in this client and its
+    /// clones. Returns whether a rule was removed.
+    pub fn remove_rule(&self, from: &str) -> bool {
+        self.rules.remove_rule(from)
+    }
+
+    /// Adds a `from -> to` rule restricted to requests with one of `methods`, in this client and
+    /// its clones. See [`RewireRules::add_method_rule`].
+    pub fn add_method_rule(
+        &self,
+        methods: impl IntoIterator<Item = Method>,
+        from: &str,
+        to: &str,
+    ) -> Result<(), RewireError> {
+        self.rules.add_method_rule(methods, from, to)
+    }
+
+    /// Removes the rules restricted to some of `methods` matching exactly the same requests as
+    /// `from`, in this client and its clones. Returns whether a rule was removed.
+    pub fn remove_method_rule(
+        &self,
+        methods: impl IntoIterator<Item = Method>,
+        from: &str,
+    ) -> bool {
+        self.rules.remove_method_rule(methods, from)
+    }
+
+    /// Adds a `from -> to` rule restricted to requests carrying every `name: value` pair of
+    /// `headers`, in this client and its clones. See [`RewireRules::add_header_rule`].
+    pub fn add_header_rule<N, V>(
+        &self,
+        headers: impl IntoIterator<Item = (N, V)>,
+        from: &str,
+        to: &str,
+    ) -> Result<(), RewireError>
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.rules.add_header_rule(headers, from, to)
+    }
+
+    /// Removes the rule requiring exactly `headers` matching exactly the same requests as `from`,
+    /// in this client and its clones. Returns whether a rule was removed.
+    pub fn remove_header_rule<N, V>(
+        &self,
+        headers: impl IntoIterator<Item = (N, V)>,
+        from: &str,
+    ) -> bool
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.rules.remove_header_rule(headers, from)
+    }
+
+    /// Adds a rule rewiring the URLs matching the regular expression `pattern` to `replacement`,
+    /// in this client and its clones. See [`RewireRules::add_regex_rule`].
+    #[cfg(feature = "regex")]
+    pub fn add_regex_rule(&self, pattern: &str, replacement: &str) -> Result<(), RewireError> {
+        self.rules.add_regex_rule(pattern, replacement)
+    }
+
+    /// Removes the regex rule with the pattern `pattern`, in this client and its clones. Returns
+    /// whether a rule was removed.
+    #[cfg(feature = "regex")]
+    pub fn remove_regex_rule(&self, pattern: &str) -> bool {
+        self.rules.remove_regex_rule(pattern)
+    }
+
+    /// Removes every rule, in this client and its clones.
+    pub fn clear(&self) {
+        self.rules.clear();
     }
 
-    /// Sends a request to a rewired URL if it is in the client's `redirects` hashmap, or else to the URL in method call.
+    /// Rewires requests matching `from` to `to` until the returned [`RuleGuard`] is dropped,
+    /// taking precedence over the rule registered for the same source, if any.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
-    /// use reqwest_rewire::rewire_client::RewireClient;
-    /// use reqwest_rewire::testable_client::TestableClient;
+    /// use reqwest_rewire::RewireClient;
     ///
-    /// let mut redirects: HashMap<String, String> = HashMap::new();
-    /// redirects.insert("http://localhost:8080/example".into_string(), "http://localhost:8080/rewired".into_string());
-    /// let client = RewireClient::new(redirects);
-    /// client.request(http::Method::GET, "http://localhost:8080/example").send().await.unwrap(); // hits http://localhost:8080/rewired
-    /// client.request(http::Method::GET, "http://localhost:8080/example/nested_path").send().await.unwrap(); // hits http://localhost:8080/rewired/nested_path
-    /// client.request(http::Method::GET, "http://localhost:8080/example?foo=bar").send().await.unwrap(); // hits http://localhost:8080/rewired?foo=bar
+    /// let client = RewireClient::builder()
+    ///     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+    ///     .build()
+    ///     .unwrap();
+    /// {
+    ///     let _guard = client
+    ///         .override_rule("https://real-api.com/api/", "http://localhost:3500/failing-mock/")
+    ///         .unwrap();
+    ///     // requests to https://real-api.com/api/ hit http://localhost:3500/failing-mock/
+    /// }
+    /// // requests to https://real-api.com/api/ hit http://localhost:3000/api-mock/ again
     /// ```
-    fn request(&self, method: Method, url: &str) -> RequestBuilder {
-        self.client.request(method, self.get_url(url).as_ref())
+    pub fn override_rule(&self, from: &str, to: &str) -> Result<RuleGuard, RewireError> {
+        self.rules.override_rule(from, to)
+    }
+
+    /// The URL a request is rewired to, if any rule matches it, or why it must not be sent.
+    pub(crate) fn rewired_url(
+        &self,
+        method: &Method,
+        url: &Url,
+        headers: &HeaderMap,
+    ) -> Result<Option<Url>, RewireError> {
+        self.policy
+            .resolve(&self.rules, self.journal.as_ref(), method, url, headers)
+    }
+}
+
+impl TestableClient for RewireClient {
+    fn reqwest_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Rewrites the URL of `request` with the most specific matching rule.
+    /// If no rule matches, the client's [`UnmatchedPolicy`](crate::UnmatchedPolicy) applies.
+    fn rewire_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        if let Some(rewired) =
+            self.rewired_url(request.method(), request.url(), request.headers())?
+        {
+            *request.url_mut() = rewired;
+        }
+        Ok(())
+    }
+
+    fn rewire_built_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        self.rewire_request(request)?;
+        self.rewired.insert(request.method(), request.url());
+        Ok(())
+    }
+
+    fn rewire_executed_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        if self.rewired.take(request.method(), request.url()) {
+            return Ok(());
+        }
+        self.rewire_request(request)
     }
 }
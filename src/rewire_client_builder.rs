@@ -0,0 +1,287 @@
+use crate::request_policy::RequestPolicy;
+use crate::rewired_requests::RewiredRequests;
+use crate::unused_rule_report::UnusedRuleReport;
+use crate::{
+    BlockingRewireClient, GenericRewireClient, Journal, RewireClient, RewireError, RewireRules,
+    RewireRulesBuilder, Rule, UnmatchedPolicy, UnusedRulePolicy,
+};
+use reqwest::Method;
+use std::sync::Arc;
+
+/// A builder to create a [`RewireClient`], or a [`BlockingRewireClient`] when `C` is
+/// `reqwest::blocking::Client`, with custom rules and configuration.
+///
+/// Rules are registered and validated like those of a [`RewireRulesBuilder`]: in strict mode (the
+/// default), an invalid or duplicated rule makes `build` fail with a [`RewireError`]. Otherwise,
+/// invalid rules are ignored and later rules replace earlier duplicates.
+///
+/// # Examples
+///
+/// ```
+/// use reqwest_rewire::RewireClient;
+///
+/// let client = RewireClient::builder()
+///     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+///     .rewire("https://auth.real-api.com/", "http://localhost:3001/")
+///     .with_reqwest_client(reqwest::Client::new())
+///     .build()
+///     .unwrap();
+/// ```
+#[must_use]
+pub struct RewireClientBuilder<C = reqwest::Client> {
+    rules: RewireRulesBuilder,
+    client: Option<C>,
+    unmatched: UnmatchedPolicy,
+    allowed_hosts: Vec<String>,
+    denied_hosts: Vec<String>,
+    journal: bool,
+    unused_rules: UnusedRulePolicy,
+}
+
+/// A builder to create a [`BlockingRewireClient`], with the same options as a [`RewireClient`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use reqwest_rewire::BlockingRewireClient;
+///
+/// let client = BlockingRewireClient::builder()
+///     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+///     .with_reqwest_client(reqwest::blocking::Client::new())
+///     .build()
+///     .unwrap();
+/// ```
+pub type BlockingRewireClientBuilder = RewireClientBuilder<reqwest::blocking::Client>;
+
+/// A builder to create a [`RewireMiddleware`](crate::RewireMiddleware), with the same options as
+/// a [`RewireClient`] except the reqwest client, which belongs to the middleware stack.
+///
+/// # Examples
+///
+/// ```
+/// use reqwest_rewire::{RewireMiddleware, UnmatchedPolicy};
+///
+/// let middleware = RewireMiddleware::builder()
+///     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+///     .unmatched(UnmatchedPolicy::Error)
+///     .build()
+///     .unwrap();
+/// ```
+#[cfg(feature = "middleware")]
+pub type RewireMiddlewareBuilder = RewireClientBuilder<()>;
+
+impl<C> Default for RewireClientBuilder<C> {
+    fn default() -> Self {
+        Self {
+            rules: RewireRulesBuilder::new(),
+            client: None,
+            unmatched: UnmatchedPolicy::default(),
+            allowed_hosts: Vec::new(),
+            denied_hosts: Vec::new(),
+            journal: false,
+            unused_rules: UnusedRulePolicy::default(),
+        }
+    }
+}
+
+impl<C> RewireClientBuilder<C> {
+    /// Rewires requests matching `from` to `to`.
+    pub fn rewire(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.rules = self.rules.rewire(from, to);
+        self
+    }
+
+    /// Rewires every `from -> to` pair of `rules`, e.g. a `HashMap<String, String>`.
+    pub fn rewires<F, T>(mut self, rules: impl IntoIterator<Item = (F, T)>) -> Self
+    where
+        F: Into<String>,
+        T: Into<String>,
+    {
+        self.rules = self.rules.rewires(rules);
+        self
+    }
+
+    /// Registers `rule`. See [`RewireRulesBuilder::rule`].
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rules = self.rules.rule(rule);
+        self
+    }
+
+    /// Rewires requests matching `from` to `to`, only for requests with one of `methods`. See
+    /// [`RewireRulesBuilder::rewire_methods`].
+    pub fn rewire_methods(
+        mut self,
+        methods: impl IntoIterator<Item = Method>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        self.rules = self.rules.rewire_methods(methods, from, to);
+        self
+    }
+
+    /// Rewires requests matching `from` to `to`, only for requests carrying every `name: value`
+    /// pair of `headers`. See [`RewireRulesBuilder::rewire_headers`].
+    pub fn rewire_headers<N, V>(
+        mut self,
+        headers: impl IntoIterator<Item = (N, V)>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.rules = self.rules.rewire_headers(headers, from, to);
+        self
+    }
+
+    /// Rewires the URLs matching the regular expression `pattern` to `replacement`. See
+    /// [`RewireRulesBuilder::rewire_regex`].
+    #[cfg(feature = "regex")]
+    pub fn rewire_regex(
+        mut self,
+        pattern: impl Into<String>,
+        replacement: impl Into<String>,
+    ) -> Self {
+        self.rules = self.rules.rewire_regex(pattern, replacement);
+        self
+    }
+
+    /// Registers the enabled rules of `config` in order, and applies its `strict` setting if any.
+    #[cfg(feature = "config")]
+    pub fn config(mut self, config: crate::RewireConfig) -> Self {
+        self.rules = self.rules.config(config);
+        self
+    }
+
+    /// Sets whether invalid or duplicated rules make `build` fail. Defaults to `true`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.rules = self.rules.strict(strict);
+        self
+    }
+
+    /// Sets what the client does with requests matching no rule. Defaults to
+    /// [`UnmatchedPolicy::Allow`], sending them to their original URL.
+    pub fn unmatched(mut self, policy: UnmatchedPolicy) -> Self {
+        self.unmatched = policy;
+        self
+    }
+
+    /// Lets unmatched requests to hosts matching `pattern` through, whatever the
+    /// [`unmatched`](Self::unmatched) policy. Rules still apply to these hosts.
+    ///
+    /// A pattern is a domain (`localhost`), a wildcard matching any subdomain of a domain
+    /// (`*.internal.example.com`), an IP address (`127.0.0.1`, `::1`) or a CIDR network
+    /// (`127.0.0.0/8`). Domains are compared case-insensitively, and ports are ignored. An invalid
+    /// pattern makes `build` fail with [`RewireError::InvalidHostPattern`].
+    pub fn allow_host(mut self, pattern: impl Into<String>) -> Self {
+        self.allowed_hosts.push(pattern.into());
+        self
+    }
+
+    /// Blocks requests to hosts matching `pattern`, with the syntax of
+    /// [`allow_host`](Self::allow_host). Requests are checked once rewired, so a rule redirecting
+    /// a denied host elsewhere still works. Denied hosts take precedence over allowed ones, and
+    /// blocked requests fail with [`RewireError::Blocked`].
+    pub fn deny_host(mut self, pattern: impl Into<String>) -> Self {
+        self.denied_hosts.push(pattern.into());
+        self
+    }
+
+    /// Sets whether the client records every request in a [`Journal`](crate::Journal), shared
+    /// with its clones. Defaults to `false`.
+    pub fn journal(mut self, enabled: bool) -> Self {
+        self.journal = enabled;
+        self
+    }
+
+    /// Sets what the client reports, once its last clone is dropped, about the rules that never
+    /// rewrote a request. Defaults to [`UnusedRulePolicy::Ignore`].
+    pub fn unused_rules(mut self, policy: UnusedRulePolicy) -> Self {
+        self.unused_rules = policy;
+        self
+    }
+
+    /// Validates the rules and returns a client sending requests through the given reqwest client,
+    /// or a default one.
+    fn into_client(self) -> Result<GenericRewireClient<C>, RewireError>
+    where
+        C: Default,
+    {
+        let parts = self.into_parts()?;
+        Ok(GenericRewireClient {
+            rules: parts.rules,
+            client: parts.client.unwrap_or_default(),
+            policy: parts.policy,
+            journal: parts.journal,
+            rewired: RewiredRequests::default(),
+            _unused_rule_report: parts.unused_rule_report,
+        })
+    }
+
+    /// Validates the rules and the request policy, and returns them with the journal and unused
+    /// rule report of a new client.
+    fn into_parts(self) -> Result<ClientParts<C>, RewireError> {
+        let rules = self.rules.build()?;
+        Ok(ClientParts {
+            policy: RequestPolicy::new(self.unmatched, &self.allowed_hosts, &self.denied_hosts)?,
+            journal: self.journal.then(Journal::default),
+            unused_rule_report: UnusedRuleReport::new(&rules, self.unused_rules).map(Arc::new),
+            rules,
+            client: self.client,
+        })
+    }
+}
+
+/// The validated parts of a client, shared by every kind of client a builder creates.
+struct ClientParts<C> {
+    rules: RewireRules,
+    policy: RequestPolicy,
+    journal: Option<Journal>,
+    unused_rule_report: Option<Arc<UnusedRuleReport>>,
+    client: Option<C>,
+}
+
+impl RewireClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends requests through `client` instead of a default `reqwest::Client`.
+    pub fn with_reqwest_client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Validates the rules and returns the client.
+    pub fn build(self) -> Result<RewireClient, RewireError> {
+        self.into_client()
+    }
+}
+
+impl BlockingRewireClientBuilder {
+    /// Sends requests through `client` instead of a default `reqwest::blocking::Client`.
+    pub fn with_reqwest_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Validates the rules and returns the client.
+    pub fn build(self) -> Result<BlockingRewireClient, RewireError> {
+        self.into_client()
+    }
+}
+
+#[cfg(feature = "middleware")]
+impl RewireMiddlewareBuilder {
+    /// Validates the rules and returns the middleware.
+    pub fn build(self) -> Result<crate::RewireMiddleware, RewireError> {
+        let parts = self.into_parts()?;
+        Ok(crate::RewireMiddleware {
+            rules: parts.rules,
+            policy: parts.policy,
+            journal: parts.journal,
+            _unused_rule_report: parts.unused_rule_report,
+        })
+    }
+}
@@ -0,0 +1,196 @@
+use crate::ConfigError;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Redirect rules loaded from a configuration file, applied with
+/// [`RewireRulesBuilder::config`](crate::RewireRulesBuilder::config), the `config` method of the
+/// client builders, or [`RewireClient::from_config_file`](crate::RewireClient::from_config_file).
+///
+/// Rules are kept in the order they are written. The same structure is read from TOML, JSON and
+/// YAML, e.g. in TOML:
+///
+/// ```toml
+/// strict = true
+///
+/// [[rules]]
+/// source = "https://real-api.com/api/"
+/// target = "http://localhost:3000/api-mock/"
+///
+/// [[rules]]
+/// source = "https://auth.real-api.com/"
+/// target = "http://localhost:3001/"
+/// options = { enabled = false }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RewireConfig {
+    /// Overrides the builder's [`strict`](crate::RewireRulesBuilder::strict) setting when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+    /// The rules, in the order they are registered.
+    #[serde(default)]
+    pub rules: Vec<RuleConfig>,
+}
+
+/// A single `source -> target` rule of a [`RewireConfig`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleConfig {
+    pub source: String,
+    pub target: String,
+    #[serde(default, skip_serializing_if = "RuleOptions::is_default")]
+    pub options: RuleOptions,
+}
+
+/// Optional settings of a [`RuleConfig`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuleOptions {
+    /// Whether the rule is registered. Defaults to `true`, so a rule can be turned off without
+    /// removing it from the file.
+    pub enabled: bool,
+}
+
+impl Default for RuleOptions {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+impl RuleOptions {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The file formats a [`RewireConfig`] can be read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Guesses the format of `path` from its extension.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        match path.as_ref().extension()?.to_str()? {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+impl RewireConfig {
+    /// Reads a configuration from `path`, whose format is guessed from its extension.
+    ///
+    /// Parse errors carry the path, line and column of the offending content.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let format =
+            ConfigFormat::from_path(path).ok_or_else(|| ConfigError::UnsupportedFormat {
+                path: path.to_path_buf(),
+            })?;
+        let content = std::fs::read_to_string(path).map_err(|error| ConfigError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        Self::parse(&content, format).map_err(|error| match error {
+            ConfigError::Parse {
+                line,
+                column,
+                message,
+                ..
+            } => ConfigError::Parse {
+                path: Some(path.to_path_buf()),
+                line,
+                column,
+                message,
+            },
+            error => error,
+        })
+    }
+
+    /// Parses a configuration written in `format`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reqwest_rewire::{ConfigFormat, RewireConfig};
+    ///
+    /// let config = RewireConfig::parse(
+    ///     r#"{ "rules": [{ "source": "https://real-api.com/", "target": "http://localhost:3000/" }] }"#,
+    ///     ConfigFormat::Json,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(config.rules[0].target, "http://localhost:3000/");
+    /// ```
+    pub fn parse(content: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
+        match format {
+            ConfigFormat::Toml => toml::from_str(content).map_err(|error| {
+                let (line, column) = error
+                    .span()
+                    .map(|span| line_and_column(content, span.start))
+                    .unzip();
+                parse_error(line, column, error.message().to_string())
+            }),
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|error| {
+                let (line, column) = (error.line(), error.column());
+                parse_error(
+                    Some(line),
+                    Some(column),
+                    strip_location(&error, line, column),
+                )
+            }),
+            ConfigFormat::Yaml => serde_yaml_ng::from_str(content).map_err(|error| {
+                let (line, column) = error
+                    .location()
+                    .map(|location| (location.line(), location.column()))
+                    .unzip();
+                let message = match (line, column) {
+                    (Some(line), Some(column)) => strip_location(&error, line, column),
+                    _ => error.to_string(),
+                };
+                parse_error(line, column, message)
+            }),
+        }
+    }
+
+    /// The `source -> target` pairs of the enabled rules, in order.
+    pub(crate) fn enabled_rules(&self) -> impl Iterator<Item = (String, String)> + '_ {
+        self.rules
+            .iter()
+            .filter(|rule| rule.options.enabled)
+            .map(|rule| (rule.source.clone(), rule.target.clone()))
+    }
+}
+
+fn parse_error(line: Option<usize>, column: Option<usize>, message: String) -> ConfigError {
+    ConfigError::Parse {
+        path: None,
+        line,
+        column,
+        message,
+    }
+}
+
+/// The 1-based line and column of the byte at `offset` in `content`.
+fn line_and_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    (line, column)
+}
+
+/// The message of `error` without the ` at line L column C` suffix added by serde_json and
+/// serde_yaml_ng, since the location is reported separately.
+fn strip_location(error: &impl std::fmt::Display, line: usize, column: usize) -> String {
+    let message = error.to_string();
+    let suffix = format!(" at line {} column {}", line, column);
+    match message.strip_suffix(&suffix) {
+        Some(message) => message.to_string(),
+        None => message,
+    }
+}
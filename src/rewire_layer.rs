@@ -0,0 +1,147 @@
+use crate::rule_table::RequestHead;
+use crate::{RewireError, RewireRules, RewireRulesBuilder};
+use http::header::HOST;
+use http::uri::PathAndQuery;
+use http::{HeaderValue, Request, Uri};
+use std::collections::HashMap;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A [`tower_layer::Layer`] rewriting the URI of every `http::Request` with the same rules as a
+/// [`RewireClient`](crate::RewireClient), for clients built on `hyper` or `tower`.
+///
+/// Absolute URIs are rewritten in place. Origin-form URIs (path and query only) are matched
+/// against `http://` and the `Host` header. When a request carries a `Host` header, it is updated
+/// to the rewired host and port.
+///
+/// # Examples
+///
+/// ```
+/// use reqwest_rewire::{RewireClient, RewireLayer};
+///
+/// let client = RewireClient::builder()
+///     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+///     .build()
+///     .unwrap();
+/// // The layer shares the rules of the reqwest client.
+/// let layer = RewireLayer::from_rules(client.rules().clone());
+/// ```
+#[derive(Clone)]
+pub struct RewireLayer {
+    rules: RewireRules,
+}
+
+impl RewireLayer {
+    /// Creates a layer from `redirects`, ignoring invalid rules.
+    pub fn new(redirects: HashMap<String, String>) -> Self {
+        Self::from_rules(RewireRules::new(redirects))
+    }
+
+    /// Creates a layer from `redirects`, failing if any rule is invalid instead of ignoring it.
+    pub fn try_new(redirects: HashMap<String, String>) -> Result<Self, RewireError> {
+        RewireRules::try_new(redirects).map(Self::from_rules)
+    }
+
+    /// Creates a layer applying an existing rule set.
+    pub fn from_rules(rules: RewireRules) -> Self {
+        Self { rules }
+    }
+
+    /// Creates a [`RewireRulesBuilder`] to register rules, finished with
+    /// [`build_layer`](RewireRulesBuilder::build_layer).
+    pub fn builder() -> RewireRulesBuilder {
+        RewireRulesBuilder::new()
+    }
+}
+
+impl<S> Layer<S> for RewireLayer {
+    type Service = RewireService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RewireService {
+            inner,
+            rules: self.rules.clone(),
+        }
+    }
+}
+
+/// The [`tower_service::Service`] produced by [`RewireLayer`], rewiring requests before handing
+/// them to the inner service.
+#[derive(Clone)]
+pub struct RewireService<S> {
+    inner: S,
+    rules: RewireRules,
+}
+
+impl<S, B> Service<Request<B>> for RewireService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        rewire_request(&self.rules, &mut request);
+        self.inner.call(request)
+    }
+}
+
+/// Rewrites the URI and `Host` header of `request`, leaving it untouched if no rule matches.
+fn rewire_request<B>(rules: &RewireRules, request: &mut Request<B>) {
+    let absolute = request.uri().authority().is_some();
+    let uri = if absolute {
+        Some(request.uri().clone())
+    } else {
+        absolute_uri(request)
+    };
+    let head = RequestHead {
+        method: Some(request.method()),
+        headers: Some(request.headers()),
+    };
+    let Some(rewired) = uri.and_then(|uri| rules.rewrite_request_uri(head, &uri)) else {
+        return;
+    };
+
+    if request.headers().contains_key(HOST)
+        && let Some(host) = rewired.host()
+    {
+        let host = match rewired.port_u16() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        };
+        if let Ok(value) = HeaderValue::from_str(&host) {
+            request.headers_mut().insert(HOST, value);
+        }
+    }
+
+    *request.uri_mut() = if absolute {
+        rewired
+    } else {
+        rewired
+            .path_and_query()
+            .cloned()
+            .unwrap_or_else(|| PathAndQuery::from_static("/"))
+            .into()
+    };
+}
+
+/// Rebuilds the absolute URI of an origin-form request from its `Host` header.
+fn absolute_uri<B>(request: &Request<B>) -> Option<Uri> {
+    let host = request.headers().get(HOST)?.to_str().ok()?;
+    let path_and_query = request
+        .uri()
+        .path_and_query()
+        .map_or("/", PathAndQuery::as_str);
+    Uri::builder()
+        .scheme("http")
+        .authority(host)
+        .path_and_query(path_and_query)
+        .build()
+        .ok()
+}
@@ -0,0 +1,83 @@
+use crate::request_policy::RequestPolicy;
+use crate::unused_rule_report::UnusedRuleReport;
+use crate::{Journal, RewireError, RewireMiddlewareBuilder, RewireRules};
+use http::Extensions;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A [`reqwest_middleware::Middleware`] rewriting request URLs with the same rules as a
+/// [`RewireClient`](crate::RewireClient), so rewiring composes with the rest of a middleware stack.
+///
+/// # Examples
+///
+/// ```
+/// use reqwest_rewire::RewireMiddleware;
+///
+/// let middleware = RewireMiddleware::builder()
+///     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+///     .build()
+///     .unwrap();
+/// let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+///     .with(middleware)
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct RewireMiddleware {
+    pub(crate) rules: RewireRules,
+    pub(crate) policy: RequestPolicy,
+    pub(crate) journal: Option<Journal>,
+    /// Only held to report unused rules when the last clone is dropped.
+    pub(crate) _unused_rule_report: Option<Arc<UnusedRuleReport>>,
+}
+
+impl RewireMiddleware {
+    /// Creates a middleware from `redirects`, ignoring invalid rules.
+    pub fn new(redirects: HashMap<String, String>) -> Self {
+        Self::builder()
+            .rewires(redirects)
+            .strict(false)
+            .build()
+            .expect("non-strict builds never fail")
+    }
+
+    /// Creates a middleware from `redirects`, failing if any rule is invalid instead of ignoring it.
+    pub fn try_new(redirects: HashMap<String, String>) -> Result<Self, RewireError> {
+        Self::builder().rewires(redirects).build()
+    }
+
+    /// Creates a [`RewireMiddlewareBuilder`] to configure a middleware.
+    pub fn builder() -> RewireMiddlewareBuilder {
+        RewireMiddlewareBuilder::default()
+    }
+
+    /// The journal of the requests handled by this middleware and its clones, or `None` unless it
+    /// was enabled with [`journal`](RewireMiddlewareBuilder::journal).
+    pub fn journal(&self) -> Option<&Journal> {
+        self.journal.as_ref()
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RewireMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        match self.policy.resolve(
+            &self.rules,
+            self.journal.as_ref(),
+            req.method(),
+            req.url(),
+            req.headers(),
+        ) {
+            Ok(Some(rewired)) => *req.url_mut() = rewired,
+            Ok(None) => {}
+            Err(error) => return Err(reqwest_middleware::Error::middleware(error)),
+        }
+        next.run(req, extensions).await
+    }
+}
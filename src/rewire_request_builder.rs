@@ -0,0 +1,179 @@
+use crate::testable_client::execute_rewired;
+use crate::{Error, TestableClient};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Body, Request, RequestBuilder, Response, Version};
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+/// A request builder returned by the request methods of
+/// [`TestableClientExt`](crate::TestableClientExt), such as
+/// [`get`](crate::TestableClientExt::get).
+///
+/// It wraps a `reqwest::RequestBuilder` and the client it was started from. The request is
+/// rewired once, when it is built or sent, so rules can match on the method and headers set on
+/// the builder.
+///
+/// # Examples
+///
+/// ```no_run
+/// use reqwest_rewire::{RewireClient, TestableClientExt};
+///
+/// # async fn run() {
+/// let client = RewireClient::builder()
+///     .rewire_headers(
+///         [("accept", "application/vnd.v2+json")],
+///         "http://localhost:8080/example",
+///         "http://localhost:8080/v2",
+///     )
+///     .build()
+///     .unwrap();
+/// client
+///     .get("http://localhost:8080/example")
+///     .header("accept", "application/vnd.v2+json")
+///     .send()
+///     .await
+///     .unwrap(); // hits http://localhost:8080/v2
+/// # }
+/// ```
+#[must_use = "RewireRequestBuilder does nothing until you 'send' it"]
+pub struct RewireRequestBuilder<'a, C: ?Sized> {
+    client: &'a C,
+    builder: RequestBuilder,
+}
+
+impl<'a, C: TestableClient + ?Sized> RewireRequestBuilder<'a, C> {
+    pub(crate) fn new(client: &'a C, builder: RequestBuilder) -> Self {
+        Self { client, builder }
+    }
+
+    /// Adds a header to the request. See `reqwest::RequestBuilder::header`.
+    pub fn header<K, V>(self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.map(|builder| builder.header(key, value))
+    }
+
+    /// Adds a set of headers to the request. See `reqwest::RequestBuilder::headers`.
+    pub fn headers(self, headers: HeaderMap) -> Self {
+        self.map(|builder| builder.headers(headers))
+    }
+
+    /// Enables HTTP basic authentication. See `reqwest::RequestBuilder::basic_auth`.
+    pub fn basic_auth<U, P>(self, username: U, password: Option<P>) -> Self
+    where
+        U: fmt::Display,
+        P: fmt::Display,
+    {
+        self.map(|builder| builder.basic_auth(username, password))
+    }
+
+    /// Enables HTTP bearer authentication. See `reqwest::RequestBuilder::bearer_auth`.
+    pub fn bearer_auth<T: fmt::Display>(self, token: T) -> Self {
+        self.map(|builder| builder.bearer_auth(token))
+    }
+
+    /// Sets the request body. See `reqwest::RequestBuilder::body`.
+    pub fn body<T: Into<Body>>(self, body: T) -> Self {
+        self.map(|builder| builder.body(body))
+    }
+
+    /// Sets a timeout for the request. See `reqwest::RequestBuilder::timeout`.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.map(|builder| builder.timeout(timeout))
+    }
+
+    /// Appends `query` to the query string of the request URL. See
+    /// `reqwest::RequestBuilder::query`.
+    pub fn query<T: serde::Serialize + ?Sized>(self, query: &T) -> Self {
+        self.map(|builder| builder.query(query))
+    }
+
+    /// Sets a form body. See `reqwest::RequestBuilder::form`.
+    pub fn form<T: serde::Serialize + ?Sized>(self, form: &T) -> Self {
+        self.map(|builder| builder.form(form))
+    }
+
+    /// Sets a JSON body. See `reqwest::RequestBuilder::json`.
+    #[cfg(feature = "json")]
+    pub fn json<T: serde::Serialize + ?Sized>(self, json: &T) -> Self {
+        self.map(|builder| builder.json(json))
+    }
+
+    /// Sets a multipart/form-data body. See `reqwest::RequestBuilder::multipart`.
+    #[cfg(feature = "multipart")]
+    pub fn multipart(self, multipart: reqwest::multipart::Form) -> Self {
+        self.map(|builder| builder.multipart(multipart))
+    }
+
+    /// Sets the HTTP version of the request. See `reqwest::RequestBuilder::version`.
+    pub fn version(self, version: Version) -> Self {
+        self.map(|builder| builder.version(version))
+    }
+
+    /// Applies `f` to the wrapped `reqwest::RequestBuilder`, e.g. to call methods of reqwest
+    /// features not forwarded by this builder. The request is still rewired when built or sent.
+    pub fn map(self, f: impl FnOnce(RequestBuilder) -> RequestBuilder) -> Self {
+        Self {
+            client: self.client,
+            builder: f(self.builder),
+        }
+    }
+
+    /// Attempts to clone the builder, which fails if the body is a stream.
+    pub fn try_clone(&self) -> Option<Self> {
+        Some(Self {
+            client: self.client,
+            builder: self.builder.try_clone()?,
+        })
+    }
+
+    /// Builds the request and rewires it, so it can be sent by any client, e.g. a plain
+    /// `reqwest::Client` or a middleware stack.
+    ///
+    /// The client remembers the request, so [`execute`](crate::TestableClientExt::execute) sends it as it is
+    /// instead of rewiring it a second time. A [`RewireError`](crate::RewireError) refusing the
+    /// request is returned as [`Error::Rewire`].
+    pub fn build(self) -> Result<Request, Error> {
+        let mut request = self.builder.build()?;
+        self.client.rewire_built_request(&mut request)?;
+        Ok(request)
+    }
+
+    /// Builds and rewires the request like [`build`](Self::build), also returning the
+    /// `reqwest::Client` embedded in the builder. See `reqwest::RequestBuilder::build_split`.
+    pub fn build_split(self) -> (reqwest::Client, Result<Request, Error>) {
+        let (client, request) = self.builder.build_split();
+        let request = request.map_err(Error::from).and_then(|mut request| {
+            self.client.rewire_built_request(&mut request)?;
+            Ok(request)
+        });
+        (client, request)
+    }
+
+    /// Rewires the request, then sends it.
+    ///
+    /// A [`RewireError`](crate::RewireError) refusing the request is returned as
+    /// [`Error::Rewire`], without sending anything.
+    pub fn send(self) -> impl Future<Output = Result<Response, Error>> + Send + use<C> {
+        let request = self
+            .builder
+            .build()
+            .map_err(Error::from)
+            .and_then(|mut request| {
+                self.client.rewire_request(&mut request)?;
+                Ok(request)
+            });
+        execute_rewired(self.client, request)
+    }
+}
+
+impl<C: ?Sized> fmt::Debug for RewireRequestBuilder<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.builder.fmt(f)
+    }
+}
@@ -0,0 +1,254 @@
+use crate::rule_table::{RequestHead, Rewrite, RuleTable};
+use crate::{RewireError, RewireRulesBuilder, RuleGuard};
+use http::Uri;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Url};
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A compiled set of rewrite rules, independent of any HTTP client.
+///
+/// This is the rule engine behind [`RewireClient`](crate::RewireClient) and the other rewiring
+/// clients. Cloning it is cheap and shares the same rules, so one rule set can serve a reqwest
+/// client and a tower stack alike. Rules can be added and removed at any time, and every clone
+/// sees the change from its next request on.
+///
+/// # Examples
+///
+/// ```
+/// use reqwest_rewire::RewireRules;
+///
+/// let rules = RewireRules::builder()
+///     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+///     .build()
+///     .unwrap();
+/// let uri: http::Uri = "https://real-api.com/api/users?id=42".parse().unwrap();
+/// assert_eq!(
+///     rules.rewrite_uri(&uri).unwrap(),
+///     "http://localhost:3000/api-mock/users?id=42"
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct RewireRules {
+    table: Arc<RwLock<RuleTable>>,
+}
+
+impl RewireRules {
+    /// Creates a rule set from `redirects`, ignoring invalid rules.
+    pub fn new(redirects: HashMap<String, String>) -> Self {
+        Self::builder()
+            .rewires(redirects)
+            .strict(false)
+            .build()
+            .expect("non-strict builds never fail")
+    }
+
+    /// Creates a rule set from `redirects`, failing if any rule is invalid instead of ignoring it.
+    pub fn try_new(redirects: HashMap<String, String>) -> Result<Self, RewireError> {
+        Self::builder().rewires(redirects).build()
+    }
+
+    /// Creates a [`RewireRulesBuilder`] to register rules.
+    pub fn builder() -> RewireRulesBuilder {
+        RewireRulesBuilder::new()
+    }
+
+    pub(crate) fn from_table(table: RuleTable) -> Self {
+        Self {
+            table: Arc::new(RwLock::new(table)),
+        }
+    }
+
+    /// Adds a `from -> to` rule, replacing any rule matching exactly the same requests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reqwest_rewire::RewireRules;
+    ///
+    /// let rules = RewireRules::default();
+    /// let shared = rules.clone();
+    /// rules.add_rule("https://real-api.com/api/", "http://localhost:3000/").unwrap();
+    ///
+    /// let url = reqwest::Url::parse("https://real-api.com/api/users").unwrap();
+    /// assert_eq!(shared.rewrite(&url).unwrap().as_str(), "http://localhost:3000/users");
+    /// ```
+    pub fn add_rule(&self, from: &str, to: &str) -> Result<(), RewireError> {
+        self.write().insert(from, to, true)
+    }
+
+    /// Removes the rule matching exactly the same requests as `from`, e.g. `http://api/v1/` for
+    /// a rule added as `http://api:80/v1`. Returns whether a rule was removed.
+    pub fn remove_rule(&self, from: &str) -> bool {
+        self.write().remove(from).is_some()
+    }
+
+    /// Adds a `from -> to` rule restricted to requests with one of `methods`, replacing the rules
+    /// for these methods matching exactly the same requests. See
+    /// [`rewire_methods`](RewireRulesBuilder::rewire_methods).
+    pub fn add_method_rule(
+        &self,
+        methods: impl IntoIterator<Item = Method>,
+        from: &str,
+        to: &str,
+    ) -> Result<(), RewireError> {
+        let methods: Vec<Method> = methods.into_iter().collect();
+        self.write()
+            .insert_conditional(&methods, Vec::new(), from, to, true)
+    }
+
+    /// Removes the rules restricted to some of `methods` matching exactly the same requests as
+    /// `from`. Rules for any method are kept. Returns whether a rule was removed.
+    pub fn remove_method_rule(
+        &self,
+        methods: impl IntoIterator<Item = Method>,
+        from: &str,
+    ) -> bool {
+        let methods: Vec<Method> = methods.into_iter().collect();
+        self.write().remove_for_methods(&methods, from)
+    }
+
+    /// Adds a `from -> to` rule restricted to requests carrying every `name: value` pair of
+    /// `headers`, replacing the rule requiring the same headers for the same requests, if any.
+    /// See [`rewire_headers`](RewireRulesBuilder::rewire_headers).
+    pub fn add_header_rule<N, V>(
+        &self,
+        headers: impl IntoIterator<Item = (N, V)>,
+        from: &str,
+        to: &str,
+    ) -> Result<(), RewireError>
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        let headers = header_pairs(headers);
+        self.write().insert_for_headers(&headers, from, to, true)
+    }
+
+    /// Removes the rule requiring exactly `headers` matching exactly the same requests as `from`,
+    /// if any. Returns whether a rule was removed.
+    pub fn remove_header_rule<N, V>(
+        &self,
+        headers: impl IntoIterator<Item = (N, V)>,
+        from: &str,
+    ) -> bool
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        let headers = header_pairs(headers);
+        self.write().remove_for_headers(&headers, from)
+    }
+
+    /// Adds a rule rewiring the URLs matching the regular expression `pattern` to `replacement`,
+    /// replacing any regex rule with the same pattern. See
+    /// [`rewire_regex`](RewireRulesBuilder::rewire_regex) for the syntax.
+    #[cfg(feature = "regex")]
+    pub fn add_regex_rule(&self, pattern: &str, replacement: &str) -> Result<(), RewireError> {
+        self.write().insert_regex(pattern, replacement, true)
+    }
+
+    /// Removes the regex rule with the pattern `pattern`. Returns whether a rule was removed.
+    #[cfg(feature = "regex")]
+    pub fn remove_regex_rule(&self, pattern: &str) -> bool {
+        self.write().remove_regex(pattern)
+    }
+
+    /// Removes every rule.
+    pub fn clear(&self) {
+        self.write().clear();
+    }
+
+    /// Layers a `from -> to` rule over the rule matching exactly the same requests, until the
+    /// returned [`RuleGuard`] is dropped.
+    pub fn override_rule(&self, from: &str, to: &str) -> Result<RuleGuard, RewireError> {
+        let id = self.write().insert_override(from, to)?;
+        Ok(RuleGuard::new(self.clone(), from.to_string(), id))
+    }
+
+    pub(crate) fn remove_override(&self, from: &str, id: u64) {
+        self.write().remove_override(from, id);
+    }
+
+    pub(crate) fn sources(&self) -> Vec<String> {
+        self.read().sources()
+    }
+
+    pub(crate) fn unused_sources(&self) -> Vec<String> {
+        self.read().unused_sources()
+    }
+
+    /// Rewrites `url` with the most specific matching rule, or returns `None` if no rule matches.
+    /// Rules restricted to some methods or headers never match, see
+    /// [`rewrite_request`](Self::rewrite_request).
+    pub fn rewrite(&self, url: &Url) -> Option<Url> {
+        self.resolve(RequestHead::default(), url)
+            .map(|rewrite| rewrite.url)
+    }
+
+    /// Rewrites a `method` request to `url` with the most specific matching rule, or returns
+    /// `None` if no rule matches. Rules restricted to some headers never match, see
+    /// [`rewrite_with_headers`](Self::rewrite_with_headers).
+    pub fn rewrite_request(&self, method: &Method, url: &Url) -> Option<Url> {
+        let head = RequestHead {
+            method: Some(method),
+            headers: None,
+        };
+        self.resolve(head, url).map(|rewrite| rewrite.url)
+    }
+
+    /// Rewrites a `method` request to `url` carrying `headers` with the most specific matching
+    /// rule, or returns `None` if no rule matches.
+    pub fn rewrite_with_headers(
+        &self,
+        method: &Method,
+        url: &Url,
+        headers: &HeaderMap,
+    ) -> Option<Url> {
+        let head = RequestHead {
+            method: Some(method),
+            headers: Some(headers),
+        };
+        self.resolve(head, url).map(|rewrite| rewrite.url)
+    }
+
+    /// Rewrites a `head` request to `url` like
+    /// [`rewrite_with_headers`](Self::rewrite_with_headers), along with the rule applied.
+    pub(crate) fn resolve(&self, head: RequestHead, url: &Url) -> Option<Rewrite> {
+        self.read().rewrite(head, url)
+    }
+
+    /// Rewrites an absolute `uri` with the most specific matching rule, or returns `None` if no
+    /// rule matches. URIs without a scheme and authority never match, and neither do rules
+    /// restricted to some methods or headers.
+    pub fn rewrite_uri(&self, uri: &Uri) -> Option<Uri> {
+        self.rewrite_request_uri(RequestHead::default(), uri)
+    }
+
+    /// Rewrites a `head` request to an absolute `uri` like [`rewrite_uri`](Self::rewrite_uri).
+    pub(crate) fn rewrite_request_uri(&self, head: RequestHead, uri: &Uri) -> Option<Uri> {
+        let url = Url::parse(&uri.to_string()).ok()?;
+        self.resolve(head, &url)?.url.as_str().parse().ok()
+    }
+
+    // Rule updates cannot panic halfway, so a poisoned table is still consistent.
+    fn read(&self) -> RwLockReadGuard<'_, RuleTable> {
+        self.table.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, RuleTable> {
+        self.table.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The `name: value` pairs of `headers`, as owned strings.
+pub(crate) fn header_pairs<N, V>(headers: impl IntoIterator<Item = (N, V)>) -> Vec<(String, String)>
+where
+    N: Into<String>,
+    V: Into<String>,
+{
+    headers
+        .into_iter()
+        .map(|(name, value)| (name.into(), value.into()))
+        .collect()
+}
@@ -0,0 +1,229 @@
+use crate::rewire_rules::header_pairs;
+use crate::rule::RuleKind;
+use crate::rule_table::{HeaderRule, RuleTable};
+use crate::{RewireError, RewireRules, Rule};
+use reqwest::Method;
+
+/// A builder to create a [`RewireRules`] rule set, not tied to any client.
+///
+/// Rules are validated when [`build`](RewireRulesBuilder::build) is called. In strict mode (the
+/// default), an invalid or duplicated rule makes `build` fail with a [`RewireError`]. Otherwise,
+/// invalid rules are ignored and later rules replace earlier duplicates. The client builders
+/// register their rules through one of these.
+///
+/// # Examples
+///
+/// ```
+/// use reqwest_rewire::RewireRules;
+///
+/// let rules = RewireRules::builder()
+///     .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+///     .rewire("https://auth.real-api.com/", "http://localhost:3001/")
+///     .build()
+///     .unwrap();
+/// ```
+#[must_use]
+pub struct RewireRulesBuilder {
+    rules: Vec<(String, String)>,
+    method_rules: Vec<(Vec<Method>, String, String)>,
+    header_rules: Vec<HeaderRule>,
+    #[cfg(feature = "regex")]
+    regex_rules: Vec<(String, String)>,
+    strict: bool,
+}
+
+impl Default for RewireRulesBuilder {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            method_rules: Vec::new(),
+            header_rules: Vec::new(),
+            #[cfg(feature = "regex")]
+            regex_rules: Vec::new(),
+            strict: true,
+        }
+    }
+}
+
+impl RewireRulesBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rewires requests matching `from` to `to`.
+    pub fn rewire(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.rules.push((from.into(), to.into()));
+        self
+    }
+
+    /// Rewires every `from -> to` pair of `rules`, e.g. a `HashMap<String, String>`.
+    pub fn rewires<F, T>(mut self, rules: impl IntoIterator<Item = (F, T)>) -> Self
+    where
+        F: Into<String>,
+        T: Into<String>,
+    {
+        self.rules
+            .extend(rules.into_iter().map(|(from, to)| (from.into(), to.into())));
+        self
+    }
+
+    /// Registers `rule`, e.g. a [`Rule::prefix`] or a `Rule::regex`.
+    pub fn rule(self, rule: Rule) -> Self {
+        match rule.0 {
+            RuleKind::Prefix { from, to } => self.rewire(from, to),
+            #[cfg(feature = "regex")]
+            RuleKind::Regex {
+                pattern,
+                replacement,
+            } => self.rewire_regex(pattern, replacement),
+        }
+    }
+
+    /// Rewires requests matching `from` to `to`, only for requests with one of `methods`.
+    ///
+    /// For a request with one of `methods`, a rule restricted to its method takes precedence over
+    /// the rule for any method with the same source, but a rule with a longer matching path still
+    /// wins, and so does an [`override_rule`](crate::RewireClient::override_rule) of the same
+    /// source. Rules sharing a method and matching exactly the same requests are duplicates, and
+    /// an empty `methods` makes a rule for any method, like [`rewire`](Self::rewire).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reqwest::Method;
+    /// use reqwest_rewire::RewireClient;
+    ///
+    /// // Payments fail, but reading them still reaches the real API.
+    /// let client = RewireClient::builder()
+    ///     .rewire_methods(
+    ///         [Method::POST],
+    ///         "https://api.com/payments",
+    ///         "http://localhost:3000/fail",
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn rewire_methods(
+        mut self,
+        methods: impl IntoIterator<Item = Method>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        self.method_rules
+            .push((methods.into_iter().collect(), from.into(), to.into()));
+        self
+    }
+
+    /// Rewires requests matching `from` to `to`, only for requests carrying every `name: value`
+    /// pair of `headers`.
+    ///
+    /// Header names are compared case-insensitively, and a value matches a header whose whole
+    /// value, or one of whose comma-separated items, is equal to it. For a request meeting their
+    /// conditions, rules restricted to headers take precedence over the rule without conditions
+    /// with the same source, those requiring more headers first, but rules restricted to its
+    /// method and [overrides](crate::RewireClient::override_rule) of that source come before them,
+    /// and a rule with a longer matching path still wins. Between rules requiring as many headers,
+    /// the one whose headers sorted by name then value come first applies, whatever the order the
+    /// rules were added in. An invalid header name or value makes `build` fail with
+    /// [`RewireError::InvalidHeader`].
+    ///
+    /// Requests started with [`get`](crate::TestableClientExt::get) and the other request methods
+    /// are rewired when built or sent, so these rules match on the headers set on their builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reqwest_rewire::RewireClient;
+    ///
+    /// let client = RewireClient::builder()
+    ///     .rewire("https://api.com/", "http://localhost:3001/")
+    ///     .rewire_headers(
+    ///         [("accept", "application/vnd.v2+json")],
+    ///         "https://api.com/",
+    ///         "http://localhost:3002/",
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn rewire_headers<N, V>(
+        mut self,
+        headers: impl IntoIterator<Item = (N, V)>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.header_rules
+            .push((header_pairs(headers), from.into(), to.into()));
+        self
+    }
+
+    /// Rewires the URLs matching the regular expression `pattern` to `replacement`, in which
+    /// captures are written `$1` or `${name}`.
+    ///
+    /// The pattern must match the whole request URL, query and fragment excluded. The query of the
+    /// request is appended to that of the replacement, and its fragment is kept. Regex rules are
+    /// tried before prefix rules, in the order they were added, and the first match wins. They
+    /// match on the URL only, so they also take precedence over the prefix rules restricted to
+    /// methods or headers. The one exception is an active
+    /// [`override_rule`](crate::RewireClient::override_rule): when it is the prefix rule a request
+    /// would use, regex rules are skipped. An invalid pattern makes `build` fail with
+    /// [`RewireError::InvalidRegex`].
+    ///
+    /// This is the same as registering a [`Rule::regex`](crate::Rule::regex).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reqwest_rewire::RewireClient;
+    ///
+    /// let client = RewireClient::builder()
+    ///     .rewire_regex(r"https://api\.com/v(\d+)/(.*)", "http://localhost:3000/$2?version=$1")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn rewire_regex(
+        mut self,
+        pattern: impl Into<String>,
+        replacement: impl Into<String>,
+    ) -> Self {
+        self.regex_rules.push((pattern.into(), replacement.into()));
+        self
+    }
+
+    /// Registers the enabled rules of `config` in order, and applies its `strict` setting if any.
+    #[cfg(feature = "config")]
+    pub fn config(mut self, config: crate::RewireConfig) -> Self {
+        self.rules.extend(config.enabled_rules());
+        if let Some(strict) = config.strict {
+            self.strict = strict;
+        }
+        self
+    }
+
+    /// Sets whether invalid or duplicated rules make [`build`](RewireRulesBuilder::build) fail.
+    /// Defaults to `true`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Validates the rules and returns them.
+    pub fn build(self) -> Result<RewireRules, RewireError> {
+        let table = RuleTable::from_rules(&self.rules, self.strict)?
+            .with_method_rules(&self.method_rules, self.strict)?
+            .with_header_rules(&self.header_rules, self.strict)?;
+        #[cfg(feature = "regex")]
+        let table = table.with_regexes(&self.regex_rules, self.strict)?;
+        Ok(RewireRules::from_table(table))
+    }
+
+    /// Validates the rules and returns them as a [`RewireLayer`](crate::RewireLayer).
+    #[cfg(feature = "tower")]
+    pub fn build_layer(self) -> Result<crate::RewireLayer, RewireError> {
+        self.build().map(crate::RewireLayer::from_rules)
+    }
+}
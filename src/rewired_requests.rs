@@ -0,0 +1,41 @@
+use reqwest::{Method, Url};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// The requests built and rewired by a client but not executed by it yet, shared by its clones.
+///
+/// Requests cannot carry a mark of their own, so they are identified by their method and rewired
+/// URL. [`execute`](crate::TestableClientExt::execute) takes them out to send them as they are,
+/// instead of rewiring them a second time.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RewiredRequests {
+    requests: Arc<Mutex<HashMap<(Method, Url), usize>>>,
+}
+
+impl RewiredRequests {
+    /// Remembers a request rewired to `url`.
+    pub(crate) fn insert(&self, method: &Method, url: &Url) {
+        *self
+            .lock()
+            .entry((method.clone(), url.clone()))
+            .or_default() += 1;
+    }
+
+    /// Forgets a request rewired to `url`, returning whether there was one.
+    pub(crate) fn take(&self, method: &Method, url: &Url) -> bool {
+        let mut requests = self.lock();
+        let key = (method.clone(), url.clone());
+        let Some(count) = requests.get_mut(&key) else {
+            return false;
+        };
+        *count -= 1;
+        if *count == 0 {
+            requests.remove(&key);
+        }
+        true
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<(Method, Url), usize>> {
+        self.requests.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
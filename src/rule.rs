@@ -0,0 +1,60 @@
+/// A rewrite rule, registered on a builder with [`rule`](crate::RewireRulesBuilder::rule).
+///
+/// # Examples
+///
+/// ```
+/// use reqwest_rewire::{RewireClient, Rule};
+///
+/// let client = RewireClient::builder()
+///     .rule(Rule::prefix("https://real-api.com/api/", "http://localhost:3000/api-mock/"))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule(pub(crate) RuleKind);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RuleKind {
+    Prefix {
+        from: String,
+        to: String,
+    },
+    #[cfg(feature = "regex")]
+    Regex {
+        pattern: String,
+        replacement: String,
+    },
+}
+
+impl Rule {
+    /// A rule rewiring requests matching `from` to `to`, like
+    /// [`rewire`](crate::RewireRulesBuilder::rewire).
+    pub fn prefix(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self(RuleKind::Prefix {
+            from: from.into(),
+            to: to.into(),
+        })
+    }
+
+    /// A rule rewiring the URLs matching the regular expression `pattern` to `replacement`, in
+    /// which captures are written `$1` or `${name}`. See
+    /// [`rewire_regex`](crate::RewireRulesBuilder::rewire_regex) for how it is matched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reqwest_rewire::{RewireClient, Rule};
+    ///
+    /// let client = RewireClient::builder()
+    ///     .rule(Rule::regex(r"https://api\.com/v(\d+)/(.*)", "http://localhost:3000/$2?version=$1"))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: impl Into<String>, replacement: impl Into<String>) -> Self {
+        Self(RuleKind::Regex {
+            pattern: pattern.into(),
+            replacement: replacement.into(),
+        })
+    }
+}
@@ -0,0 +1,32 @@
+use crate::RewireRules;
+
+/// A temporary rule returned by [`RewireClient::override_rule`](crate::RewireClient::override_rule),
+/// removed when the guard is dropped.
+///
+/// While the guard is alive, its rule takes precedence over any rule registered for the same
+/// source, including older overrides and rules restricted to some methods or headers. Requests
+/// matching a rule with a longer path are still sent to that rule's target. Dropping the guard
+/// restores the rule it was layered over.
+#[must_use = "the override is removed as soon as the guard is dropped"]
+pub struct RuleGuard {
+    rules: RewireRules,
+    source: String,
+    id: u64,
+}
+
+impl RuleGuard {
+    pub(crate) fn new(rules: RewireRules, source: String, id: u64) -> Self {
+        Self { rules, source, id }
+    }
+
+    /// The source URL of the overriding rule.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl Drop for RuleGuard {
+    fn drop(&mut self) {
+        self.rules.remove_override(&self.source, self.id);
+    }
+}
@@ -0,0 +1,967 @@
+use crate::RewireError;
+#[cfg(feature = "regex")]
+use crate::regex_rule::RegexRule;
+use crate::target_template::{TargetTemplate, is_placeholder_name};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, Url};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A single redirect rule, parsed once when the table is built.
+pub(crate) struct Rule {
+    pub(crate) source: String,
+    pub(crate) target: Url,
+    /// The target placeholders to fill with the captures of each request, if any.
+    template: Option<TargetTemplate>,
+    /// Number of path segments in the source prefix, trailing slash and `{name*}` segment excluded.
+    depth: usize,
+    /// The `{name}` segments of the source path, by index.
+    params: Vec<(usize, String)>,
+    /// The name of the trailing `{name*}` segment of the source path, if any.
+    rest: Option<String>,
+    /// The methods of the requests this rule applies to, or none if it applies to every request.
+    methods: Vec<Method>,
+    /// The headers the requests this rule applies to must carry, each with the value given.
+    headers: Vec<(HeaderName, HeaderValue)>,
+    /// Whether this rule was layered over others with
+    /// [`insert_override`](RuleTable::insert_override).
+    overriding: bool,
+    /// Number of requests rewritten with this rule, counted while the table is only read.
+    hits: AtomicU64,
+}
+
+/// The headers a rule requires as `name: value` pairs, then its source and target.
+pub(crate) type HeaderRule = (Vec<(String, String)>, String, String);
+
+/// The parts of a request rules can match besides its URL. A part left out meets no condition.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct RequestHead<'a> {
+    pub(crate) method: Option<&'a Method>,
+    pub(crate) headers: Option<&'a HeaderMap>,
+}
+
+impl Rule {
+    /// The source of this rule, prefixed with its methods and followed by its headers if it is
+    /// restricted to some, e.g. `POST https://api.com/payments` or
+    /// `https://api.com/ [accept: application/vnd.v2+json]`.
+    pub(crate) fn label(&self) -> String {
+        let mut label = self.source.clone();
+        if !self.methods.is_empty() {
+            let methods: Vec<&str> = self.methods.iter().map(Method::as_str).collect();
+            label = format!("{} {}", methods.join(","), label);
+        }
+        if !self.headers.is_empty() {
+            let headers: Vec<String> = self
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    format!("{}: {}", name, String::from_utf8_lossy(value.as_bytes()))
+                })
+                .collect();
+            label = format!("{} [{}]", label, headers.join(", "));
+        }
+        label
+    }
+
+    fn applies_to(&self, head: RequestHead) -> bool {
+        let method_matches = self.methods.is_empty()
+            || head
+                .method
+                .is_some_and(|method| self.methods.contains(method));
+        method_matches
+            && self.headers.iter().all(|(name, value)| {
+                head.headers
+                    .is_some_and(|headers| carries(headers, name, value))
+            })
+    }
+
+    /// How narrow the conditions of this rule are: restricted to some methods first, then by the
+    /// number of headers required. Only compared between rules of the same source path.
+    fn specificity(&self) -> (bool, usize) {
+        (!self.methods.is_empty(), self.headers.len())
+    }
+
+    /// The headers this rule requires, sorted by name then value. Between rules as specific as
+    /// each other, the one whose sorted headers come first applies, whatever the order the rules
+    /// were added in.
+    fn sorted_headers(&self) -> Vec<(&str, &[u8])> {
+        let mut headers: Vec<(&str, &[u8])> = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_bytes()))
+            .collect();
+        headers.sort_unstable();
+        headers
+    }
+
+    /// Whether this rule and `other`, registered for the same source, would both apply to some
+    /// request without one being more specific: they share a method, or have no methods and
+    /// require the same headers.
+    fn conflicts_with(&self, other: &Rule) -> bool {
+        if self.methods.is_empty() && other.methods.is_empty() {
+            return self.headers.len() == other.headers.len()
+                && self
+                    .headers
+                    .iter()
+                    .all(|header| other.headers.contains(header));
+        }
+        self.methods
+            .iter()
+            .any(|method| other.methods.contains(method))
+    }
+
+    /// Rewrites `url`, which must match this rule's source prefix, onto the rule's target.
+    ///
+    /// The path segments following the matched prefix are appended to the target path with a
+    /// single `/`, whether or not the source or target end with a slash. A trailing slash on the
+    /// request is kept. When nothing follows the prefix, the target path is used as written.
+    /// The request query, fragment and credentials replace those of the target, if any. Path
+    /// segments are copied in their percent-encoded form, so they round-trip unchanged.
+    /// Placeholders in the target are first replaced with the values in `captures` and the path
+    /// segments bound by the source template. A `{name*}` segment binds the whole remainder, so
+    /// nothing is appended after it. Returns `None` if the filled target is not a valid URL.
+    fn apply(&self, url: &Url, captures: &[(String, String)]) -> Option<Url> {
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(Iterator::collect)
+            .unwrap_or_default();
+        let target = match &self.template {
+            Some(template) => {
+                let mut captures = captures.to_vec();
+                for (index, name) in &self.params {
+                    captures.push((name.clone(), segments[*index].to_string()));
+                }
+                if let Some(name) = &self.rest {
+                    captures.push((name.clone(), segments[self.depth..].join("/")));
+                }
+                self.fill(template, &captures)?
+            }
+            None => self.target.clone(),
+        };
+        let mut remainder = segments.get(self.depth..).unwrap_or_default().to_vec();
+        if (self.depth == 0 && remainder == [""]) || self.rest.is_some() {
+            remainder.clear();
+        }
+
+        let mut rewired = target;
+        if !remainder.is_empty() {
+            let base = rewired.path().trim_end_matches('/').to_string();
+            rewired.set_path(&format!("{}/{}", base, remainder.join("/")));
+        }
+        if let Some(query) = url.query() {
+            rewired.set_query(Some(query));
+        }
+        if let Some(fragment) = url.fragment() {
+            rewired.set_fragment(Some(fragment));
+        }
+        if !url.username().is_empty() || url.password().is_some() {
+            // Only fails for targets without a host, which are rejected when rules are added.
+            let _ = rewired.set_username(url.username());
+            let _ = rewired.set_password(url.password());
+        }
+        Some(rewired)
+    }
+
+    /// Fills the placeholders of `template` with `captures`. Returns `None` if the result is not a
+    /// valid URL, e.g. a host made of a captured `a%20b` segment, or if an empty capture left an
+    /// empty path segment the target does not have, e.g. `http://mock//x` for the target
+    /// `http://mock/{p}/x` and an empty `{p*}` segment.
+    fn fill(&self, template: &TargetTemplate, captures: &[(String, String)]) -> Option<Url> {
+        let filled = Url::parse(&template.fill(captures)).ok()?;
+        (empty_segments(&filled) == empty_segments(&self.target)).then_some(filled)
+    }
+}
+
+/// The number of empty segments in the path of `url`, a trailing slash excluded.
+fn empty_segments(url: &Url) -> usize {
+    let path = url.path().strip_suffix('/').unwrap_or(url.path());
+    path.split('/')
+        .skip(1)
+        .filter(|segment| segment.is_empty())
+        .count()
+}
+
+/// The outcome of rewriting a request with a rule.
+pub(crate) struct Rewrite {
+    pub(crate) url: Url,
+    /// The [label](Rule::label) of the rule applied, or the pattern of a regex rule, which is
+    /// how rules are identified in journals and reports.
+    pub(crate) rule: String,
+}
+
+/// Precompiled redirect rules, indexed by origin then by path prefix.
+///
+/// Each origin (scheme, host and effective port) owns a trie keyed on path segments, so finding
+/// the most specific rule for a request only walks the request path once, whatever the number of
+/// rules registered. Template segments add branches to that walk, tried after literal ones.
+/// Origins whose host has `*` labels are also listed in `wildcards`, and only tried when no rule of
+/// the request's exact origin matches. Regex rules are tried before all of them, in the order they
+/// were added.
+#[derive(Default)]
+pub(crate) struct RuleTable {
+    origins: HashMap<Origin, PathNode>,
+    /// Wildcard origins, those with the most literal host labels first.
+    wildcards: Vec<Origin>,
+    #[cfg(feature = "regex")]
+    regexes: Vec<RegexRule>,
+    next_override: u64,
+}
+
+/// The part of a URL a rule must match exactly: scheme, host and port.
+/// Default ports are made explicit, so `http://api/` and `http://api:80/` share an origin.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Origin {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+}
+
+impl Origin {
+    fn of(url: &Url) -> Option<Self> {
+        Some(Self {
+            scheme: url.scheme().to_string(),
+            host: url.host_str()?.to_string(),
+            port: url.port_or_known_default(),
+        })
+    }
+
+    fn is_wildcard(&self) -> bool {
+        self.host.split('.').any(|label| label == "*")
+    }
+
+    /// Matches the host of `origin` against this wildcard origin, each `*` label standing for
+    /// exactly one label. Returns the captured labels, named `1`, `2`... from left to right.
+    fn captures(&self, origin: &Origin) -> Option<Vec<(String, String)>> {
+        if self.scheme != origin.scheme || self.port != origin.port {
+            return None;
+        }
+        let pattern: Vec<&str> = self.host.split('.').collect();
+        let host: Vec<&str> = origin.host.split('.').collect();
+        if pattern.len() != host.len() {
+            return None;
+        }
+        let mut captures = Vec::new();
+        for (pattern, label) in pattern.into_iter().zip(host) {
+            if pattern == "*" && !label.is_empty() {
+                captures.push(((captures.len() + 1).to_string(), label.to_string()));
+            } else if pattern != label {
+                return None;
+            }
+        }
+        Some(captures)
+    }
+}
+
+/// A segment of a rule source path.
+enum Segment<'a> {
+    Literal(&'a str),
+    /// `{name}`, matching any single segment.
+    Param(&'a str),
+    /// `{name*}`, matching every remaining segment, if any.
+    Rest(&'a str),
+}
+
+impl<'a> Segment<'a> {
+    /// Parses a percent-encoded segment, in which braces are written `%7B` and `%7D`.
+    fn parse(segment: &'a str) -> Self {
+        let name = segment
+            .strip_prefix("%7B")
+            .and_then(|name| name.strip_suffix("%7D"));
+        match name {
+            Some(name) if is_placeholder_name(name) => Segment::Param(name),
+            Some(name) => match name.strip_suffix('*') {
+                Some(name) if is_placeholder_name(name) => Segment::Rest(name),
+                _ => Segment::Literal(segment),
+            },
+            None => Segment::Literal(segment),
+        }
+    }
+}
+
+/// The rank of a match: the number of literal segments matched, then of segments matched, then
+/// the [specificity](Rule::specificity) of the rule.
+type Rank = (usize, usize, (bool, usize));
+
+#[derive(Default)]
+struct PathNode {
+    rule: Option<Rule>,
+    /// Temporary rules layered over `rule` by id, the last one taking precedence.
+    overrides: Vec<(u64, Rule)>,
+    children: HashMap<String, PathNode>,
+    /// The node of `{name}` segments at this position.
+    param: Option<Box<PathNode>>,
+    /// The node of a `{name*}` segment at this position.
+    rest: Option<Box<PathNode>>,
+    /// Rules restricted to some methods or headers, taking precedence over `rule`, but not over
+    /// `overrides`, for the requests meeting their conditions. No two of them conflict.
+    conditional_rules: Vec<Rule>,
+}
+
+impl PathNode {
+    /// The rule applied to requests ending their match on this node, for any method.
+    fn active_rule(&self) -> Option<&Rule> {
+        self.overrides
+            .last()
+            .map(|(_, rule)| rule)
+            .or(self.rule.as_ref())
+    }
+
+    /// The rule applied to `head` requests ending their match on this node: the last override,
+    /// else the most specific conditional rule they meet the conditions of, the one whose
+    /// [sorted headers](Rule::sorted_headers) come first between rules as specific, else the rule
+    /// for any request.
+    fn rule_for(&self, head: RequestHead) -> Option<&Rule> {
+        if let Some((_, rule)) = self.overrides.last() {
+            return Some(rule);
+        }
+        self.conditional_rules
+            .iter()
+            .filter(|rule| rule.applies_to(head))
+            .max_by(|rule, other| {
+                rule.specificity()
+                    .cmp(&other.specificity())
+                    .then_with(|| other.sorted_headers().cmp(&rule.sorted_headers()))
+            })
+            .or(self.rule.as_ref())
+    }
+
+    /// The child node of `segment`, created if needed.
+    fn child(&mut self, segment: &Segment) -> &mut PathNode {
+        match segment {
+            Segment::Literal(segment) => self.children.entry(segment.to_string()).or_default(),
+            Segment::Param(_) => self.param.get_or_insert_default(),
+            Segment::Rest(_) => self.rest.get_or_insert_default(),
+        }
+    }
+
+    /// Takes a rule out of the node at the end of `segments` with `take`, then prunes the nodes
+    /// left without rules.
+    fn remove(
+        &mut self,
+        segments: &[Segment],
+        take: impl FnOnce(&mut PathNode) -> Option<Rule>,
+    ) -> Option<Rule> {
+        let Some((segment, tail)) = segments.split_first() else {
+            return take(self);
+        };
+        let slot = match segment {
+            Segment::Literal(segment) => {
+                let child = self.children.get_mut(*segment)?;
+                let removed = child.remove(tail, take);
+                if child.is_empty() {
+                    self.children.remove(*segment);
+                }
+                return removed;
+            }
+            Segment::Param(_) => &mut self.param,
+            Segment::Rest(_) => &mut self.rest,
+        };
+        let child = slot.as_deref_mut()?;
+        let removed = child.remove(tail, take);
+        if child.is_empty() {
+            *slot = None;
+        }
+        removed
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rule.is_none()
+            && self.overrides.is_empty()
+            && self.conditional_rules.is_empty()
+            && self.children.is_empty()
+            && self.param.is_none()
+            && self.rest.is_none()
+    }
+
+    fn descendants(&self) -> impl Iterator<Item = &PathNode> {
+        self.children
+            .values()
+            .chain(self.param.as_deref())
+            .chain(self.rest.as_deref())
+    }
+
+    /// Returns the rule matching a `head` request to `url` in this trie, if any: the one with the
+    /// most literal segments, then the one with the most segments, then, among the rules of the
+    /// same source, a rule restricted to its method before one for any method, then the one
+    /// requiring the most of its headers. On a tie, literal segments win over template ones.
+    fn lookup(&self, head: RequestHead, url: &Url) -> Option<&Rule> {
+        let segments: Vec<&str> = prefix_segments(url).collect();
+        let mut best = None;
+        self.find(head, &segments, (0, 0), &mut best);
+        best.map(|(_, rule)| rule)
+    }
+
+    /// Ranks the rules of this node and its descendants matching `segments`, the part of the
+    /// request path left after reaching this node with `matched` literal and total segments, and
+    /// keeps the best one in `best`.
+    fn find<'a>(
+        &'a self,
+        head: RequestHead,
+        segments: &[&str],
+        matched: (usize, usize),
+        best: &mut Option<(Rank, &'a Rule)>,
+    ) {
+        keep_best(best, matched, self.rule_for(head));
+        if let Some(rest) = &self.rest {
+            let matched = (matched.0, matched.1 + segments.len());
+            keep_best(best, matched, rest.rule_for(head));
+        }
+        let Some((segment, tail)) = segments.split_first() else {
+            return;
+        };
+        if let Some(child) = self.children.get(*segment) {
+            child.find(head, tail, (matched.0 + 1, matched.1 + 1), best);
+        }
+        if let Some(param) = &self.param {
+            param.find(head, tail, (matched.0, matched.1 + 1), best);
+        }
+    }
+
+    /// Pushes the sources of the active rules of this node and its descendants to `sources`.
+    fn collect_sources(&self, sources: &mut Vec<String>) {
+        if let Some(rule) = self.active_rule() {
+            sources.push(rule.source.clone());
+        }
+        sources.extend(self.conditional_rules.iter().map(Rule::label));
+        for child in self.descendants() {
+            child.collect_sources(sources);
+        }
+    }
+
+    /// Pushes the sources of the rules of this node and its descendants that never rewrote a
+    /// request to `sources`, overridden rules and overrides included.
+    fn collect_unused(&self, sources: &mut Vec<String>) {
+        let rules = self
+            .rule
+            .iter()
+            .chain(self.overrides.iter().map(|(_, rule)| rule))
+            .chain(&self.conditional_rules);
+        for rule in rules {
+            if rule.hits.load(Ordering::Relaxed) == 0 {
+                sources.push(rule.label());
+            }
+        }
+        for child in self.descendants() {
+            child.collect_unused(sources);
+        }
+    }
+}
+
+impl RuleTable {
+    /// Parses `source -> target` pairs into a lookup table. In strict mode, the first invalid or
+    /// duplicated rule is returned as an error. Otherwise, invalid rules are ignored and later
+    /// rules replace earlier duplicates.
+    pub(crate) fn from_rules(
+        rules: &[(String, String)],
+        strict: bool,
+    ) -> Result<Self, RewireError> {
+        let mut table = Self::default();
+        for (source, target) in rules {
+            let inserted = table.insert(source, target, !strict);
+            if strict {
+                inserted?;
+            }
+        }
+        Ok(table)
+    }
+
+    /// Adds a `source -> target` rule. When `overwrite` is false, a rule matching exactly the
+    /// same requests as an existing one is rejected instead of replacing it.
+    pub(crate) fn insert(
+        &mut self,
+        source: &str,
+        target: &str,
+        overwrite: bool,
+    ) -> Result<(), RewireError> {
+        let (node, rule) = self.node_for(source, target, &[], Vec::new())?;
+        if let (Some(existing), false) = (&node.rule, overwrite) {
+            return Err(RewireError::DuplicateRule {
+                source: source.to_string(),
+                existing: existing.source.clone(),
+            });
+        }
+        node.rule = Some(rule);
+        Ok(())
+    }
+
+    /// Adds `source -> target` rules restricted to some methods, in the same way as
+    /// [`from_rules`](RuleTable::from_rules) adds rules for any method.
+    pub(crate) fn with_method_rules(
+        mut self,
+        rules: &[(Vec<Method>, String, String)],
+        strict: bool,
+    ) -> Result<Self, RewireError> {
+        for (methods, source, target) in rules {
+            let inserted = self.insert_conditional(methods, Vec::new(), source, target, !strict);
+            if strict {
+                inserted?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Adds `source -> target` rules restricted to requests carrying some headers, in the same
+    /// way as [`from_rules`](RuleTable::from_rules) adds rules for any request.
+    pub(crate) fn with_header_rules(
+        mut self,
+        rules: &[HeaderRule],
+        strict: bool,
+    ) -> Result<Self, RewireError> {
+        for (headers, source, target) in rules {
+            let inserted = self.insert_for_headers(headers, source, target, !strict);
+            if strict {
+                inserted?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Adds a `source -> target` rule restricted to requests carrying every `name: value` pair
+    /// of `headers`, like [`insert_conditional`](RuleTable::insert_conditional).
+    pub(crate) fn insert_for_headers(
+        &mut self,
+        headers: &[(String, String)],
+        source: &str,
+        target: &str,
+        overwrite: bool,
+    ) -> Result<(), RewireError> {
+        let headers = parse_headers(headers)?;
+        self.insert_conditional(&[], headers, source, target, overwrite)
+    }
+
+    /// Adds a `source -> target` rule restricted to requests with one of `methods` and carrying
+    /// `headers`, or for any request if both are empty. When `overwrite` is false, a rule
+    /// conflicting with an existing one for the same source is rejected instead of replacing it.
+    pub(crate) fn insert_conditional(
+        &mut self,
+        methods: &[Method],
+        headers: Vec<(HeaderName, HeaderValue)>,
+        source: &str,
+        target: &str,
+        overwrite: bool,
+    ) -> Result<(), RewireError> {
+        if methods.is_empty() && headers.is_empty() {
+            return self.insert(source, target, overwrite);
+        }
+        let (node, rule) = self.node_for(source, target, methods, headers)?;
+        if let (Some(existing), false) = (
+            node.conditional_rules
+                .iter()
+                .find(|other| other.conflicts_with(&rule)),
+            overwrite,
+        ) {
+            return Err(RewireError::DuplicateRule {
+                source: rule.label(),
+                existing: existing.label(),
+            });
+        }
+        node.conditional_rules
+            .retain(|other| !other.conflicts_with(&rule));
+        node.conditional_rules.push(rule);
+        Ok(())
+    }
+
+    /// Adds `pattern -> replacement` regex rules, in the same way as
+    /// [`from_rules`](RuleTable::from_rules) adds prefix rules.
+    #[cfg(feature = "regex")]
+    pub(crate) fn with_regexes(
+        mut self,
+        rules: &[(String, String)],
+        strict: bool,
+    ) -> Result<Self, RewireError> {
+        for (pattern, replacement) in rules {
+            let inserted = self.insert_regex(pattern, replacement, !strict);
+            if strict {
+                inserted?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Adds a rule rewriting the URLs matched by `pattern` into `replacement`. When `overwrite` is
+    /// false, a rule with the same pattern as an existing one is rejected instead of replacing it.
+    #[cfg(feature = "regex")]
+    pub(crate) fn insert_regex(
+        &mut self,
+        pattern: &str,
+        replacement: &str,
+        overwrite: bool,
+    ) -> Result<(), RewireError> {
+        let rule = RegexRule::new(pattern, replacement)?;
+        match self
+            .regexes
+            .iter_mut()
+            .find(|other| other.pattern == pattern)
+        {
+            Some(_) if !overwrite => Err(RewireError::DuplicateRule {
+                source: pattern.to_string(),
+                existing: pattern.to_string(),
+            }),
+            Some(existing) => {
+                *existing = rule;
+                Ok(())
+            }
+            None => {
+                self.regexes.push(rule);
+                Ok(())
+            }
+        }
+    }
+
+    /// Removes the regex rule with the pattern `pattern`, if any.
+    #[cfg(feature = "regex")]
+    pub(crate) fn remove_regex(&mut self, pattern: &str) -> bool {
+        let count = self.regexes.len();
+        self.regexes.retain(|rule| rule.pattern != pattern);
+        self.regexes.len() != count
+    }
+
+    /// Layers a `source -> target` rule over the rule matching exactly the same requests, if
+    /// any, until [`remove_override`](RuleTable::remove_override) is called with the returned id.
+    pub(crate) fn insert_override(
+        &mut self,
+        source: &str,
+        target: &str,
+    ) -> Result<u64, RewireError> {
+        let id = self.next_override;
+        let (node, mut rule) = self.node_for(source, target, &[], Vec::new())?;
+        rule.overriding = true;
+        node.overrides.push((id, rule));
+        self.next_override += 1;
+        Ok(id)
+    }
+
+    /// Parses a `source -> target` rule restricted to `methods` and `headers` and returns it with
+    /// the trie node it belongs to, created if needed.
+    fn node_for(
+        &mut self,
+        source: &str,
+        target: &str,
+        methods: &[Method],
+        headers: Vec<(HeaderName, HeaderValue)>,
+    ) -> Result<(&mut PathNode, Rule), RewireError> {
+        let source_url = Url::parse(source).map_err(|error| RewireError::InvalidSource {
+            url: source.to_string(),
+            error,
+        })?;
+        let target_url = Url::parse(target).map_err(|error| RewireError::InvalidTarget {
+            url: target.to_string(),
+            error,
+        })?;
+        for url in [&source_url, &target_url] {
+            if url.cannot_be_a_base() {
+                return Err(RewireError::CannotBeABase {
+                    url: url.to_string(),
+                });
+            }
+        }
+        let origin = Origin::of(&source_url).ok_or_else(|| RewireError::MissingHost {
+            url: source.to_string(),
+        })?;
+        let labels: Vec<&str> = origin.host.split('.').collect();
+        if labels
+            .iter()
+            .any(|label| label.contains('*') && *label != "*")
+        {
+            return Err(RewireError::InvalidWildcard {
+                url: source.to_string(),
+            });
+        }
+        let segments = source_segments(source, &source_url)?;
+        let mut params = Vec::new();
+        let mut rest = None;
+        for (index, segment) in segments.iter().enumerate() {
+            match segment {
+                Segment::Literal(_) => {}
+                Segment::Param(name) => params.push((index, name.to_string())),
+                Segment::Rest(name) => rest = Some(name.to_string()),
+            }
+        }
+        // Braces in the target are only placeholders when the source captures something, so
+        // targets such as `http://mock/?q={x}` stay valid for plain prefix rules.
+        let wildcards = labels.iter().filter(|label| **label == "*").count();
+        let template = (wildcards > 0 || !params.is_empty() || rest.is_some())
+            .then(|| TargetTemplate::parse(target))
+            .flatten();
+        if let Some(placeholder) = template
+            .iter()
+            .flat_map(|template| template.placeholders())
+            .find(|name| {
+                let captured =
+                    params.iter().any(|(_, param)| param == *name) || rest.as_ref() == Some(*name);
+                !captured
+                    && !name
+                        .parse::<usize>()
+                        .is_ok_and(|index| (1..=wildcards).contains(&index))
+            })
+        {
+            return Err(RewireError::UnknownPlaceholder {
+                target: target.to_string(),
+                placeholder: placeholder.clone(),
+            });
+        }
+
+        if origin.is_wildcard() && !self.origins.contains_key(&origin) {
+            self.wildcards.push(origin.clone());
+            self.wildcards.sort_by_key(|wildcard| {
+                Reverse(
+                    wildcard
+                        .host
+                        .split('.')
+                        .filter(|label| *label != "*")
+                        .count(),
+                )
+            });
+        }
+        let mut node = self.origins.entry(origin).or_default();
+        for segment in &segments {
+            node = node.child(segment);
+        }
+
+        let rule = Rule {
+            source: source.to_string(),
+            target: target_url,
+            template,
+            depth: segments.len() - usize::from(rest.is_some()),
+            params,
+            rest,
+            methods: methods.iter().fold(Vec::new(), |mut methods, method| {
+                if !methods.contains(method) {
+                    methods.push(method.clone());
+                }
+                methods
+            }),
+            headers,
+            overriding: false,
+            hits: AtomicU64::new(0),
+        };
+        Ok((node, rule))
+    }
+
+    /// Removes the rule matching exactly the same requests as `source`, if any. Overrides of that
+    /// rule are kept.
+    pub(crate) fn remove(&mut self, source: &str) -> Option<Rule> {
+        self.remove_with(source, |node| node.rule.take())
+    }
+
+    /// Removes the rules restricted to some of `methods` matching exactly the same requests as
+    /// `source`. Returns whether a rule was removed.
+    pub(crate) fn remove_for_methods(&mut self, methods: &[Method], source: &str) -> bool {
+        self.remove_conditional(source, |rule| {
+            rule.methods.iter().any(|method| methods.contains(method))
+        })
+    }
+
+    /// Removes the rule for any method requiring exactly `headers` and matching exactly the same
+    /// requests as `source`, if any. Returns whether a rule was removed.
+    pub(crate) fn remove_for_headers(
+        &mut self,
+        headers: &[(String, String)],
+        source: &str,
+    ) -> bool {
+        let Ok(headers) = parse_headers(headers) else {
+            return false;
+        };
+        self.remove_conditional(source, |rule| {
+            rule.methods.is_empty()
+                && rule.headers.len() == headers.len()
+                && rule.headers.iter().all(|header| headers.contains(header))
+        })
+    }
+
+    fn remove_conditional(&mut self, source: &str, is_removed: impl Fn(&Rule) -> bool) -> bool {
+        self.remove_with(source, |node| {
+            let (removed, kept): (Vec<Rule>, Vec<Rule>) =
+                std::mem::take(&mut node.conditional_rules)
+                    .into_iter()
+                    .partition(|rule| is_removed(rule));
+            node.conditional_rules = kept;
+            removed.into_iter().next()
+        })
+        .is_some()
+    }
+
+    /// Removes the override of `source` added with the id `id`, if it is still registered.
+    pub(crate) fn remove_override(&mut self, source: &str, id: u64) -> Option<Rule> {
+        self.remove_with(source, |node| {
+            let index = node.overrides.iter().position(|(other, _)| *other == id)?;
+            Some(node.overrides.remove(index).1)
+        })
+    }
+
+    fn remove_with(
+        &mut self,
+        source: &str,
+        take: impl FnOnce(&mut PathNode) -> Option<Rule>,
+    ) -> Option<Rule> {
+        let source_url = Url::parse(source).ok()?;
+        let origin = Origin::of(&source_url)?;
+        let segments = source_segments(source, &source_url).ok()?;
+        let node = self.origins.get_mut(&origin)?;
+        let removed = node.remove(&segments, take);
+        if node.is_empty() {
+            self.origins.remove(&origin);
+            self.wildcards.retain(|wildcard| *wildcard != origin);
+        }
+        removed
+    }
+
+    /// The sources of the rules requests can currently match, overridden rules excluded, sorted.
+    pub(crate) fn sources(&self) -> Vec<String> {
+        let mut sources = Vec::new();
+        for node in self.origins.values() {
+            node.collect_sources(&mut sources);
+        }
+        #[cfg(feature = "regex")]
+        sources.extend(self.regexes.iter().map(|rule| rule.pattern.clone()));
+        sources.sort();
+        sources
+    }
+
+    /// The sources of the rules that never rewrote a request since they were added, sorted.
+    pub(crate) fn unused_sources(&self) -> Vec<String> {
+        let mut sources = Vec::new();
+        for node in self.origins.values() {
+            node.collect_unused(&mut sources);
+        }
+        #[cfg(feature = "regex")]
+        sources.extend(
+            self.regexes
+                .iter()
+                .filter(|rule| rule.hits.load(Ordering::Relaxed) == 0)
+                .map(|rule| rule.pattern.clone()),
+        );
+        sources.sort();
+        sources
+    }
+
+    /// Removes every rule.
+    pub(crate) fn clear(&mut self) {
+        self.origins.clear();
+        self.wildcards.clear();
+        #[cfg(feature = "regex")]
+        self.regexes.clear();
+    }
+
+    /// Returns the most specific rule matching a `head` request to `url` and the host labels it
+    /// captures, if any. Rules of the exact origin of `url` take precedence over wildcard ones.
+    fn lookup(&self, head: RequestHead, url: &Url) -> Option<(&Rule, Vec<(String, String)>)> {
+        let origin = Origin::of(url)?;
+        if let Some(rule) = self
+            .origins
+            .get(&origin)
+            .and_then(|node| node.lookup(head, url))
+        {
+            return Some((rule, Vec::new()));
+        }
+        self.wildcards.iter().find_map(|wildcard| {
+            let captures = wildcard.captures(&origin)?;
+            let rule = self.origins.get(wildcard)?.lookup(head, url)?;
+            Some((rule, captures))
+        })
+    }
+
+    /// Rewrites a `head` request to `url` with the first matching regex rule, else with the most
+    /// specific matching prefix rule, or returns `None` if no rule matches or the target of the
+    /// matching rule cannot be filled. Regex rules are skipped when that prefix rule is an
+    /// override.
+    pub(crate) fn rewrite(&self, head: RequestHead, url: &Url) -> Option<Rewrite> {
+        let matched = self.lookup(head, url);
+        #[cfg(feature = "regex")]
+        if !matched.as_ref().is_some_and(|(rule, _)| rule.overriding)
+            && let Some(rewrite) = self.regexes.iter().find_map(|rule| rule.rewrite(url))
+        {
+            return Some(rewrite);
+        }
+        let (rule, captures) = matched?;
+        let rewired = rule.apply(url, &captures)?;
+        rule.hits.fetch_add(1, Ordering::Relaxed);
+        Some(Rewrite {
+            url: rewired,
+            rule: rule.label(),
+        })
+    }
+}
+
+/// Whether `headers` has a `name` header whose value, or one of whose comma-separated items, is
+/// `expected`.
+fn carries(headers: &HeaderMap, name: &HeaderName, expected: &HeaderValue) -> bool {
+    headers.get_all(name).iter().any(|value| {
+        value == expected
+            || value.to_str().is_ok_and(|value| {
+                value
+                    .split(',')
+                    .any(|item| item.trim().as_bytes() == expected.as_bytes())
+            })
+    })
+}
+
+/// Parses the `name: value` conditions of a rule, failing on the first invalid one.
+fn parse_headers(
+    headers: &[(String, String)],
+) -> Result<Vec<(HeaderName, HeaderValue)>, RewireError> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let invalid = || RewireError::InvalidHeader {
+                name: name.clone(),
+                value: value.clone(),
+            };
+            Ok((
+                HeaderName::try_from(name.as_str()).map_err(|_| invalid())?,
+                HeaderValue::try_from(value.as_str()).map_err(|_| invalid())?,
+            ))
+        })
+        .collect()
+}
+
+/// Keeps `rule`, which matched `matched` literal and total segments, in `best` if it ranks higher.
+fn keep_best<'a>(
+    best: &mut Option<(Rank, &'a Rule)>,
+    matched: (usize, usize),
+    rule: Option<&'a Rule>,
+) {
+    let Some(rule) = rule else {
+        return;
+    };
+    let rank = (matched.0, matched.1, rule.specificity());
+    if best.is_none_or(|(best_rank, _)| rank > best_rank) {
+        *best = Some((rank, rule));
+    }
+}
+
+/// The segments of the path of `source_url`, parsed as template segments. A `{name*}` segment
+/// must come last, and names must be unique.
+fn source_segments<'a>(source: &str, source_url: &'a Url) -> Result<Vec<Segment<'a>>, RewireError> {
+    let segments: Vec<Segment> = prefix_segments(source_url).map(Segment::parse).collect();
+    let mut names = Vec::new();
+    for (index, segment) in segments.iter().enumerate() {
+        let name = match segment {
+            Segment::Literal(_) => continue,
+            Segment::Rest(_) if index + 1 < segments.len() => None,
+            Segment::Param(name) | Segment::Rest(name) => Some(*name),
+        };
+        match name {
+            Some(name) if !names.contains(&name) => names.push(name),
+            _ => {
+                return Err(RewireError::InvalidTemplate {
+                    url: source.to_string(),
+                });
+            }
+        }
+    }
+    Ok(segments)
+}
+
+/// Path segments of `url`, ignoring the empty segment produced by a trailing slash.
+fn prefix_segments(url: &Url) -> impl Iterator<Item = &str> {
+    let mut segments: Vec<&str> = url
+        .path_segments()
+        .map(Iterator::collect)
+        .unwrap_or_default();
+    if segments.last() == Some(&"") {
+        segments.pop();
+    }
+    segments.into_iter()
+}
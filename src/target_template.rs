@@ -0,0 +1,60 @@
+/// A rule target with `{name}` placeholders, filled in with the parts of each request captured by
+/// the rule source.
+pub(crate) struct TargetTemplate {
+    raw: String,
+    placeholders: Vec<String>,
+}
+
+impl TargetTemplate {
+    /// Returns the template of `target`, or `None` if it has no placeholders. A placeholder is a
+    /// name made of ASCII letters, digits and underscores between braces, e.g. `{1}`, optionally
+    /// followed by `*` to mirror a `{name*}` source segment.
+    pub(crate) fn parse(target: &str) -> Option<Self> {
+        let placeholders: Vec<String> = placeholders(target)
+            .map(|(_, _, name)| name.to_string())
+            .collect();
+        (!placeholders.is_empty()).then(|| Self {
+            raw: target.to_string(),
+            placeholders,
+        })
+    }
+
+    /// The names of the placeholders, in the order they appear.
+    pub(crate) fn placeholders(&self) -> &[String] {
+        &self.placeholders
+    }
+
+    /// Replaces every placeholder with the value captured under its name.
+    pub(crate) fn fill(&self, captures: &[(String, String)]) -> String {
+        let mut filled = String::with_capacity(self.raw.len());
+        let mut rest = 0;
+        for (start, end, name) in placeholders(&self.raw) {
+            filled.push_str(&self.raw[rest..start]);
+            let value = captures
+                .iter()
+                .find(|(captured, _)| captured == name)
+                .map_or("", |(_, value)| value);
+            filled.push_str(value);
+            rest = end;
+        }
+        filled.push_str(&self.raw[rest..]);
+        filled
+    }
+}
+
+/// The placeholders of `template`, with the offsets of their opening brace and past their closing
+/// brace.
+fn placeholders(template: &str) -> impl Iterator<Item = (usize, usize, &str)> {
+    template.match_indices('{').filter_map(|(start, _)| {
+        let token = &template[start + 1..];
+        let end = token.find('}')?;
+        let token = &token[..end];
+        let name = token.strip_suffix('*').unwrap_or(token);
+        is_placeholder_name(name).then_some((start, start + end + 2, name))
+    })
+}
+
+/// Whether `name` is made of ASCII letters, digits and underscores only, and is not empty.
+pub(crate) fn is_placeholder_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -1,42 +1,195 @@
+use crate::{Error, RewireError, RewireRequestBuilder};
 use http::Method;
-use reqwest::{RequestBuilder};
+use reqwest::{IntoUrl, Request, Response};
+use std::future::Future;
 
+/// A client that can be swapped for a [`RewireClient`](crate::RewireClient) in tests.
+///
+/// This trait is object safe, so application code can take a `&dyn TestableClient` or a
+/// `Box<dyn TestableClient>`. The request methods (`get`, `post`...) are provided for every
+/// implementor, trait objects included, by [`TestableClientExt`].
 pub trait TestableClient {
-    fn get(&self, url: &str) -> RequestBuilder;
-    fn post(&self, url: &str) -> RequestBuilder;
-    fn put(&self, url: &str) -> RequestBuilder;
-    fn patch(&self, url: &str) -> RequestBuilder;
-    fn delete(&self, url: &str) -> RequestBuilder;
-    fn head(&self, url: &str) -> RequestBuilder;
-    fn request(&self, method: Method, url: &str) -> RequestBuilder;
+    /// The `reqwest::Client` requests are sent through.
+    fn reqwest_client(&self) -> &reqwest::Client;
+
+    /// Rewrites the URL of `request` according to the client's rules, or returns why it must not
+    /// be sent.
+    fn rewire_request(&self, request: &mut Request) -> Result<(), RewireError>;
+
+    /// Rewrites the URL of a request built to be sent later, as
+    /// [`rewire_request`](Self::rewire_request) does. A client remembering the requests it rewires
+    /// this way sends them as they are from [`execute`](TestableClientExt::execute), instead of rewiring them
+    /// a second time.
+    fn rewire_built_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        self.rewire_request(request)
+    }
+
+    /// Rewrites the URL of a request about to be executed, unless
+    /// [`rewire_built_request`](Self::rewire_built_request) rewired it already.
+    fn rewire_executed_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        self.rewire_request(request)
+    }
 }
 
-impl TestableClient for reqwest::Client {
-    fn get(&self, url: &str) -> RequestBuilder {
-        self.get(url)
+/// Request methods of a [`TestableClient`], accepting any [`IntoUrl`] like those of `reqwest::Client`.
+pub trait TestableClientExt: TestableClient {
+    /// Starts building a GET request to `url`, rewired if it matches the client's rules.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use reqwest_rewire::{RewireClient, TestableClientExt};
+    ///
+    /// # async fn run() {
+    /// let client = RewireClient::builder()
+    ///     .rewire("http://localhost:8080/example", "http://localhost:8080/rewired")
+    ///     .build()
+    ///     .unwrap();
+    /// client.get("http://localhost:8080/example").send().await.unwrap(); // hits http://localhost:8080/rewired
+    /// client.get("http://localhost:8080/example/nested_path").send().await.unwrap(); // hits http://localhost:8080/rewired/nested_path
+    /// client.get("http://localhost:8080/example?foo=bar").send().await.unwrap(); // hits http://localhost:8080/rewired?foo=bar
+    /// # }
+    /// ```
+    fn get<U: IntoUrl>(&self, url: U) -> RewireRequestBuilder<'_, Self> {
+        self.request(Method::GET, url)
+    }
+
+    /// Starts building a POST request to `url`, rewired if it matches the client's rules.
+    fn post<U: IntoUrl>(&self, url: U) -> RewireRequestBuilder<'_, Self> {
+        self.request(Method::POST, url)
+    }
+
+    /// Starts building a PUT request to `url`, rewired if it matches the client's rules.
+    fn put<U: IntoUrl>(&self, url: U) -> RewireRequestBuilder<'_, Self> {
+        self.request(Method::PUT, url)
+    }
+
+    /// Starts building a PATCH request to `url`, rewired if it matches the client's rules.
+    fn patch<U: IntoUrl>(&self, url: U) -> RewireRequestBuilder<'_, Self> {
+        self.request(Method::PATCH, url)
+    }
+
+    /// Starts building a DELETE request to `url`, rewired if it matches the client's rules.
+    fn delete<U: IntoUrl>(&self, url: U) -> RewireRequestBuilder<'_, Self> {
+        self.request(Method::DELETE, url)
+    }
+
+    /// Starts building a HEAD request to `url`, rewired if it matches the client's rules.
+    fn head<U: IntoUrl>(&self, url: U) -> RewireRequestBuilder<'_, Self> {
+        self.request(Method::HEAD, url)
+    }
+
+    /// Starts building a `method` request to `url`, rewired if it matches the client's rules.
+    ///
+    /// The request is rewired once, when [built](RewireRequestBuilder::build) or
+    /// [sent](RewireRequestBuilder::send), so rules can match on the headers set on the builder.
+    /// Invalid URLs are left to reqwest, so the error surfaces when the request is built or sent,
+    /// as [`Error::Reqwest`]. A [`RewireError`] refusing the request is
+    /// returned as [`Error::Rewire`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use reqwest_rewire::{RewireClient, TestableClient, TestableClientExt};
+    ///
+    /// # async fn run() {
+    /// let client: Box<dyn TestableClient> = Box::new(
+    ///     RewireClient::builder()
+    ///         .rewire("http://localhost:8080/example", "http://localhost:8080/rewired")
+    ///         .build()
+    ///         .unwrap(),
+    /// );
+    /// let url = reqwest::Url::parse("http://localhost:8080/example/nested_path").unwrap();
+    /// client.request(http::Method::GET, url).send().await.unwrap(); // hits http://localhost:8080/rewired/nested_path
+    /// # }
+    /// ```
+    fn request<U: IntoUrl>(&self, method: Method, url: U) -> RewireRequestBuilder<'_, Self> {
+        RewireRequestBuilder::new(self, self.reqwest_client().request(method, url))
     }
 
-    fn post(&self, url: &str) -> RequestBuilder {
-        self.post(url)
+    /// Rewires an already built `request`, then executes it like `reqwest::Client::execute`.
+    ///
+    /// A [`RewireError`] refusing the request is returned as [`Error::Rewire`], without sending
+    /// anything.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use reqwest_rewire::{RewireClient, TestableClientExt};
+    ///
+    /// # async fn run() {
+    /// let client = RewireClient::builder()
+    ///     .rewire("http://localhost:8080/example", "http://localhost:8080/rewired")
+    ///     .build()
+    ///     .unwrap();
+    /// let url = reqwest::Url::parse("http://localhost:8080/example").unwrap();
+    /// let request = reqwest::Request::new(http::Method::GET, url);
+    /// client.execute(request).await.unwrap(); // hits http://localhost:8080/rewired
+    /// # }
+    /// ```
+    fn execute(
+        &self,
+        mut request: Request,
+    ) -> impl Future<Output = Result<Response, Error>> + Send {
+        let request = self.rewire_executed_request(&mut request).map(|()| request);
+        execute_rewired(self, request.map_err(Error::from))
     }
+}
+
+impl<C: TestableClient + ?Sized> TestableClientExt for C {}
+
+impl<C: TestableClient + ?Sized> TestableClient for &C {
+    fn reqwest_client(&self) -> &reqwest::Client {
+        (**self).reqwest_client()
+    }
+
+    fn rewire_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_request(request)
+    }
+
+    fn rewire_built_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_built_request(request)
+    }
+
+    fn rewire_executed_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_executed_request(request)
+    }
+}
 
-    fn put(&self, url: &str) -> RequestBuilder {
-        self.put(url)
+impl<C: TestableClient + ?Sized> TestableClient for Box<C> {
+    fn reqwest_client(&self) -> &reqwest::Client {
+        (**self).reqwest_client()
     }
 
-    fn patch(&self, url: &str) -> RequestBuilder {
-        self.patch(url)
+    fn rewire_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_request(request)
     }
 
-    fn delete(&self, url: &str) -> RequestBuilder {
-        self.delete(url)
+    fn rewire_built_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_built_request(request)
     }
 
-    fn head(&self, url: &str) -> RequestBuilder {
-        self.head(url)
+    fn rewire_executed_request(&self, request: &mut Request) -> Result<(), RewireError> {
+        (**self).rewire_executed_request(request)
     }
+}
 
-    fn request(&self, method: Method, url: &str) -> RequestBuilder {
-        self.request(method, url)
+impl TestableClient for reqwest::Client {
+    fn reqwest_client(&self) -> &reqwest::Client {
+        self
     }
-}
\ No newline at end of file
+
+    fn rewire_request(&self, _request: &mut Request) -> Result<(), RewireError> {
+        Ok(())
+    }
+}
+
+/// Executes the rewired `request` through the reqwest client of `client`, unless it failed to
+/// build or was refused.
+pub(crate) fn execute_rewired<C: TestableClient + ?Sized>(
+    client: &C,
+    request: Result<Request, Error>,
+) -> impl Future<Output = Result<Response, Error>> + Send + use<C> {
+    let response = request.map(|request| client.reqwest_client().execute(request));
+    async move { Ok(response?.await?) }
+}
@@ -0,0 +1,16 @@
+/// What a rewiring client does with a request matching none of its rules.
+///
+/// Hosts registered with [`allow_host`](crate::RewireClientBuilder::allow_host) are always let
+/// through, whatever the policy, unless they are also denied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnmatchedPolicy {
+    /// The request is sent to its original URL.
+    #[default]
+    Allow,
+    /// The request is not sent, and fails with [`RewireError::Unmatched`](crate::RewireError::Unmatched).
+    Error,
+    /// The client panics, failing the test that sent the request. Requests to a
+    /// [denied host](crate::RewireClientBuilder::deny_host) still fail with
+    /// [`RewireError::Blocked`](crate::RewireError::Blocked) instead.
+    Panic,
+}
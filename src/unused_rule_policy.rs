@@ -0,0 +1,16 @@
+/// What a rewiring client reports when it is dropped, about the rules that never rewrote a
+/// request during its lifetime.
+///
+/// The report is made once the last clone of the client is dropped, so unused rules are caught
+/// across every clone injected in the code under test.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnusedRulePolicy {
+    /// Unused rules are not reported.
+    #[default]
+    Ignore,
+    /// Unused rules are listed on the standard error.
+    Warn,
+    /// The client panics, failing the test that dropped it. If the thread is already panicking,
+    /// unused rules are listed on the standard error instead, so the original panic is kept.
+    Panic,
+}
@@ -0,0 +1,40 @@
+use crate::{RewireRules, UnusedRulePolicy};
+use std::thread;
+
+/// Reports the unused rules of a client when the last clone holding it is dropped.
+pub(crate) struct UnusedRuleReport {
+    rules: RewireRules,
+    policy: UnusedRulePolicy,
+}
+
+impl UnusedRuleReport {
+    /// Returns a report for `rules`, or `None` if `policy` ignores unused rules.
+    pub(crate) fn new(rules: &RewireRules, policy: UnusedRulePolicy) -> Option<Self> {
+        (policy != UnusedRulePolicy::Ignore).then(|| Self {
+            rules: rules.clone(),
+            policy,
+        })
+    }
+}
+
+impl Drop for UnusedRuleReport {
+    fn drop(&mut self) {
+        let unused = self.rules.unused_sources();
+        if unused.is_empty() {
+            return;
+        }
+        let message = format!(
+            "these rules never matched a request:\n{}",
+            unused
+                .iter()
+                .map(|source| format!("    {}", source))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        // Panicking during a panic would abort the process and hide the original failure.
+        if self.policy == UnusedRulePolicy::Panic && !thread::panicking() {
+            panic!("{}", message);
+        }
+        eprintln!("warning: {}", message);
+    }
+}
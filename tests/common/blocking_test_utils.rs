@@ -0,0 +1,242 @@
+#![allow(dead_code)]
+
+use super::test_utils::{build_mock, build_query_string, get_mock};
+use httpmock::prelude::*;
+use reqwest_rewire::{BlockingTestableClient, BlockingTestableClientExt};
+use std::collections::HashMap;
+
+fn test_method(
+    client: impl BlockingTestableClient,
+    query_args: Option<HashMap<&str, &str>>,
+    body: Option<&'static str>,
+    rewire: bool,
+    server: &MockServer,
+    method: Method,
+) {
+    let mock = get_mock(server, &method, query_args.clone(), None, rewire);
+
+    let query_args_string = match query_args {
+        None => "".to_string(),
+        Some(q) => build_query_string(&q),
+    };
+
+    let request = match method {
+        GET => {
+            client
+                .get(server.url(format!("/request{}", query_args_string)))
+                .send()
+        }
+        Method::HEAD => {
+            client
+                .head(server.url(format!("/request{}", query_args_string)))
+                .send()
+        }
+        POST => {
+            client
+                .post(server.url(format!("/request{}", query_args_string)))
+                .body(body.unwrap_or(""))
+                .send()
+        }
+        PUT => {
+            client
+                .put(server.url(format!("/request{}", query_args_string)))
+                .body(body.unwrap_or(""))
+                .send()
+        }
+        DELETE => {
+            client
+                .delete(server.url(format!("/request{}", query_args_string)))
+                .body(body.unwrap_or(""))
+                .send()
+        }
+        PATCH => {
+            client
+                .patch(server.url(format!("/request{}", query_args_string)))
+                .body(body.unwrap_or(""))
+                .send()
+        }
+        _ => unreachable!(),
+    };
+
+    assert!(request.is_ok());
+    let request = request.unwrap();
+    assert_eq!(request.status(), 200);
+    let response_body = request.text();
+    assert!(response_body.is_ok());
+    let response_body = response_body.unwrap();
+
+    if let Method::HEAD = method {
+        return assert_eq!(response_body, "");
+    }
+
+    mock.assert();
+    if rewire {
+        assert_eq!(response_body, "rewire_client");
+    } else {
+        assert_eq!(response_body, "reqwest_client");
+    }
+}
+
+pub fn test_get(
+    client: impl BlockingTestableClient,
+    query_args: Option<HashMap<&str, &str>>,
+    rewire: bool,
+    server: &MockServer,
+) {
+    test_method(client, query_args, None, rewire, server, GET);
+}
+
+pub fn test_post(
+    client: impl BlockingTestableClient,
+    query_args: Option<HashMap<&str, &str>>,
+    body: Option<&'static str>,
+    rewire: bool,
+    server: &MockServer,
+) {
+    test_method(client, query_args, body, rewire, server, POST);
+}
+
+pub fn test_put(
+    client: impl BlockingTestableClient,
+    query_args: Option<HashMap<&str, &str>>,
+    body: Option<&'static str>,
+    rewire: bool,
+    server: &MockServer,
+) {
+    test_method(client, query_args, body, rewire, server, PUT);
+}
+
+pub fn test_patch(
+    client: impl BlockingTestableClient,
+    query_args: Option<HashMap<&str, &str>>,
+    body: Option<&'static str>,
+    rewire: bool,
+    server: &MockServer,
+) {
+    test_method(client, query_args, body, rewire, server, PATCH);
+}
+
+pub fn test_delete(
+    client: impl BlockingTestableClient,
+    query_args: Option<HashMap<&str, &str>>,
+    body: Option<&'static str>,
+    rewire: bool,
+    server: &MockServer,
+) {
+    test_method(client, query_args, body, rewire, server, DELETE);
+}
+
+pub fn test_head(
+    client: impl BlockingTestableClient,
+    query_args: Option<HashMap<&str, &str>>,
+    rewire: bool,
+    server: &MockServer,
+) {
+    test_method(client, query_args, None, rewire, server, Method::HEAD);
+}
+
+pub fn test_request(
+    client: impl BlockingTestableClient,
+    query_args: Option<HashMap<&str, &str>>,
+    request_body: Option<&'static str>,
+    rewire: bool,
+    server: &MockServer,
+    method: http::Method,
+) {
+    let httpmock_method = match method {
+        http::Method::GET => GET,
+        http::Method::POST => POST,
+        http::Method::PUT => PUT,
+        http::Method::DELETE => DELETE,
+        http::Method::HEAD => Method::HEAD,
+        http::Method::PATCH => PATCH,
+        _ => unreachable!(),
+    };
+
+    let mock = get_mock(server, &httpmock_method, query_args.clone(), request_body, rewire);
+
+    let query_args_string = match query_args {
+        None => "".to_string(),
+        Some(q) => build_query_string(&q),
+    };
+
+    let request = client.request(
+        method.clone(),
+        server.url(format!("/request{}", query_args_string)),
+    ).body(request_body.unwrap_or_default()).send();
+
+    assert!(request.is_ok());
+    let request = request.unwrap();
+    assert_eq!(request.status(), 200);
+    let response_body = request.text();
+    assert!(response_body.is_ok());
+    let response_body = response_body.unwrap();
+
+    if let http::Method::HEAD = method {
+        return assert_eq!(response_body, "");
+    }
+
+    mock.assert();
+    if rewire {
+        assert_eq!(response_body, "rewire_client");
+    } else {
+        assert_eq!(response_body, "reqwest_client");
+    }
+}
+
+pub fn test_request_with_nested_path(
+    client: impl BlockingTestableClient,
+    rewire: bool,
+    server: &MockServer,
+    method: http::Method,
+) {
+    let httpmock_method = match method {
+        http::Method::GET => GET,
+        http::Method::POST => POST,
+        http::Method::PUT => PUT,
+        http::Method::DELETE => DELETE,
+        http::Method::HEAD => Method::HEAD,
+        http::Method::PATCH => PATCH,
+        _ => unreachable!(),
+    };
+
+    let mock = match rewire {
+        true => build_mock(server,
+                           "/rewire_request/nested_path",
+                           &httpmock_method,
+                           None,
+                           None,
+                           "rewire_client".to_string(),
+        ),
+        false => build_mock(server,
+                            "/request/nested_path",
+                            &httpmock_method,
+                            None,
+                            None,
+                            "reqwest_client".to_string(),
+        ),
+    };
+
+    let request = client.request(
+        method.clone(),
+        server.url("/request/nested_path"),
+    ).send();
+
+    assert!(request.is_ok());
+    let request = request.unwrap();
+    assert_eq!(request.status(), 200);
+    let response_body = request.text();
+    assert!(response_body.is_ok());
+    let response_body = response_body.unwrap();
+
+    if let http::Method::HEAD = method {
+        return assert_eq!(response_body, "");
+    }
+
+    mock.assert();
+    if rewire {
+        assert_eq!(response_body, "rewire_client");
+    } else {
+        assert_eq!(response_body, "reqwest_client");
+    }
+}
\ No newline at end of file
@@ -1 +1,2 @@
-pub mod test_utils;
\ No newline at end of file
+pub mod test_utils;
+pub mod blocking_test_utils;
\ No newline at end of file
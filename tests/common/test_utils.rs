@@ -1,9 +1,12 @@
+#![allow(dead_code)]
+
 use httpmock::Mock;
 use httpmock::prelude::*;
-use reqwest_rewire::TestableClient;
+use reqwest::Url;
+use reqwest_rewire::{RewireRules, TestableClient, TestableClientExt};
 use std::collections::HashMap;
 
-fn build_query_string(query_args: &HashMap<&str, &str>) -> String {
+pub fn build_query_string(query_args: &HashMap<&str, &str>) -> String {
     if query_args.is_empty() {
         return String::new();
     }
@@ -16,6 +19,25 @@ fn build_query_string(query_args: &HashMap<&str, &str>) -> String {
 
     format!("?{}", query)
 }
+
+/// Builds strict rules from `source -> target` pairs.
+pub fn build_rules(rules: &[(&str, &str)]) -> RewireRules {
+    RewireRules::builder()
+        .rewires(rules.to_vec())
+        .build()
+        .unwrap()
+}
+
+/// The URL `rules` rewrite `url` to, if any.
+pub fn rewrite(rules: &RewireRules, url: &str) -> Option<String> {
+    rules.rewrite(&Url::parse(url).unwrap()).map(String::from)
+}
+
+/// The URL `client` sends a GET request to `url` to.
+pub fn rewired_url<C: TestableClient + ?Sized>(client: &C, url: &str) -> String {
+    client.get(url).build().unwrap().url().to_string()
+}
+
 pub fn build_rewire_test_params(server: &MockServer) -> HashMap<String, String> {
     let mut params: HashMap<String, String> = HashMap::new();
     params.insert(server.url("/request"), server.url("/rewire_request"));
@@ -23,7 +45,7 @@ pub fn build_rewire_test_params(server: &MockServer) -> HashMap<String, String>
     params
 }
 
-fn build_mock<'a>(
+pub fn build_mock<'a>(
     server: &'a MockServer,
     path: &str,
     method: &Method,
@@ -49,7 +71,7 @@ fn build_mock<'a>(
                 when_builder = when_builder.query_param(k, v);
             }
         }
-        if let Some(body) = request_body.clone() {
+        if let Some(body) = request_body {
             when_builder.body(body);
         }
         then.status(200)
@@ -58,7 +80,7 @@ fn build_mock<'a>(
     })
 }
 
-fn get_mock<'a>(
+pub fn get_mock<'a>(
     server: &'a MockServer,
     method: &Method,
     query_args: Option<HashMap<&str, &str>>,
@@ -104,40 +126,40 @@ async fn test_method(
     let request = match method {
         GET => {
             client
-                .get(&server.url(format!("/request{}", query_args_string)))
+                .get(server.url(format!("/request{}", query_args_string)))
                 .send()
                 .await
         }
         Method::HEAD => {
             client
-                .head(&server.url(format!("/request{}", query_args_string)))
+                .head(server.url(format!("/request{}", query_args_string)))
                 .send()
                 .await
         }
         POST => {
             client
-                .post(&server.url(format!("/request{}", query_args_string)))
+                .post(server.url(format!("/request{}", query_args_string)))
                 .body(body.unwrap_or(""))
                 .send()
                 .await
         }
         PUT => {
             client
-                .put(&server.url(format!("/request{}", query_args_string)))
+                .put(server.url(format!("/request{}", query_args_string)))
                 .body(body.unwrap_or(""))
                 .send()
                 .await
         }
         DELETE => {
             client
-                .delete(&server.url(format!("/request{}", query_args_string)))
+                .delete(server.url(format!("/request{}", query_args_string)))
                 .body(body.unwrap_or(""))
                 .send()
                 .await
         }
         PATCH => {
             client
-                .patch(&server.url(format!("/request{}", query_args_string)))
+                .patch(server.url(format!("/request{}", query_args_string)))
                 .body(body.unwrap_or(""))
                 .send()
                 .await
@@ -240,7 +262,7 @@ pub async fn test_request(
         _ => unreachable!(),
     };
 
-    let mock = get_mock(server, &httpmock_method, query_args.clone(), request_body.clone(), rewire);
+    let mock = get_mock(server, &httpmock_method, query_args.clone(), request_body, rewire);
 
     let query_args_string = match query_args {
         None => "".to_string(),
@@ -249,7 +271,7 @@ pub async fn test_request(
 
     let request = client.request(
         method.clone(),
-        &server.url(format!("/request{}", query_args_string)),
+        server.url(format!("/request{}", query_args_string)),
     ).body(request_body.unwrap_or_default()).send().await;
 
     assert!(request.is_ok());
@@ -306,7 +328,7 @@ pub async fn test_request_with_nested_path(
 
     let request = client.request(
         method.clone(),
-        &server.url("/request/nested_path"),
+        server.url("/request/nested_path"),
     ).send().await;
 
     assert!(request.is_ok());
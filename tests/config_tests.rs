@@ -0,0 +1,189 @@
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::test_utils::rewrite;
+use reqwest::Url;
+use reqwest_rewire::{
+    BlockingRewireClient, ConfigError, ConfigFormat, RewireClient, RewireConfig, RewireError,
+};
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn assert_fixture_rules(client: &RewireClient) {
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/users").as_deref(),
+        Some("http://localhost:3000/api-mock/users")
+    );
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/v2/users").as_deref(),
+        Some("http://localhost:3002/users")
+    );
+    assert_eq!(rewrite(client.rules(), "https://auth.real-api.com/login"), None);
+}
+
+#[test]
+fn test_from_toml_file() {
+    let client = RewireClient::from_config_file(fixture("rewire.toml")).unwrap();
+    assert_fixture_rules(&client);
+}
+
+#[test]
+fn test_from_json_file() {
+    let client = RewireClient::from_config_file(fixture("rewire.json")).unwrap();
+    assert_fixture_rules(&client);
+}
+
+#[test]
+fn test_from_yaml_file() {
+    let client = RewireClient::from_config_file(fixture("rewire.yaml")).unwrap();
+    assert_fixture_rules(&client);
+}
+
+#[test]
+fn test_formats_parse_to_the_same_config() {
+    let toml = RewireConfig::from_file(fixture("rewire.toml")).unwrap();
+    let json = RewireConfig::from_file(fixture("rewire.json")).unwrap();
+    let yaml = RewireConfig::from_file(fixture("rewire.yaml")).unwrap();
+
+    assert_eq!(toml, json);
+    assert_eq!(toml, yaml);
+    assert_eq!(toml.strict, Some(true));
+    assert_eq!(toml.rules.len(), 3);
+    assert!(!toml.rules[2].options.enabled);
+}
+
+#[test]
+fn test_blocking_from_config_file() {
+    let client = BlockingRewireClient::from_config_file(fixture("rewire.toml")).unwrap();
+    let url = Url::parse("https://real-api.com/api/users").unwrap();
+
+    assert_eq!(
+        client.rules().rewrite(&url).unwrap().as_str(),
+        "http://localhost:3000/api-mock/users"
+    );
+}
+
+#[test]
+fn test_later_rules_win_when_not_strict() {
+    let config = RewireConfig::parse(
+        r#"
+strict = false
+
+[[rules]]
+source = "https://real-api.com/api/"
+target = "http://localhost:3000/"
+
+[[rules]]
+source = "https://real-api.com/api"
+target = "http://localhost:4000/"
+"#,
+        ConfigFormat::Toml,
+    )
+    .unwrap();
+    let client = RewireClient::builder().config(config).build().unwrap();
+
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/users").as_deref(),
+        Some("http://localhost:4000/users")
+    );
+}
+
+#[test]
+fn test_duplicate_rules_fail_when_strict() {
+    let config = RewireConfig::parse(
+        r#"{ "rules": [
+            { "source": "https://real-api.com/api/", "target": "http://localhost:3000/" },
+            { "source": "https://real-api.com/api", "target": "http://localhost:4000/" }
+        ] }"#,
+        ConfigFormat::Json,
+    )
+    .unwrap();
+
+    let error = RewireClient::builder()
+        .config(config)
+        .build()
+        .err()
+        .unwrap();
+
+    assert!(matches!(error, RewireError::DuplicateRule { .. }));
+}
+
+#[test]
+fn test_toml_parse_error_location() {
+    let error = RewireConfig::parse(
+        "[[rules]]\nsource = \"https://real-api.com/\"\ntarget = 42\n",
+        ConfigFormat::Toml,
+    )
+    .unwrap_err();
+
+    let ConfigError::Parse { line, column, .. } = error else {
+        panic!("unexpected error: {}", error);
+    };
+    assert_eq!(line, Some(3));
+    assert_eq!(column, Some(10));
+}
+
+#[test]
+fn test_json_parse_error_location() {
+    let error = RewireConfig::parse(
+        "{\n  \"rules\": [\n    { \"source\": \"https://real-api.com/\" }\n  ]\n}",
+        ConfigFormat::Json,
+    )
+    .unwrap_err();
+
+    let ConfigError::Parse { line, message, .. } = error else {
+        panic!("unexpected error: {}", error);
+    };
+    assert_eq!(line, Some(3));
+    assert!(message.contains("missing field `target`"), "{}", message);
+    assert!(!message.contains("at line"), "{}", message);
+}
+
+#[test]
+fn test_yaml_file_parse_error_location() {
+    let path = fixture("invalid.yaml");
+    let error = RewireConfig::from_file(&path).unwrap_err();
+
+    let ConfigError::Parse {
+        path: error_path,
+        line,
+        message,
+        ..
+    } = &error
+    else {
+        panic!("unexpected error: {}", error);
+    };
+    assert_eq!(error_path.as_deref(), Some(std::path::Path::new(&path)));
+    assert_eq!(*line, Some(5));
+    assert!(message.contains("unknown field `tagret`"), "{}", message);
+    assert!(error.to_string().starts_with(&format!("{}:5:", path)));
+}
+
+#[test]
+fn test_invalid_rule_in_file() {
+    let error = RewireClient::from_config_file(fixture("invalid_rule.toml"))
+        .err()
+        .unwrap();
+
+    assert!(matches!(
+        error,
+        ConfigError::Rule(RewireError::InvalidTarget { .. })
+    ));
+}
+
+#[test]
+fn test_unsupported_format() {
+    let error = RewireConfig::from_file(fixture("rewire.ini")).unwrap_err();
+
+    assert!(matches!(error, ConfigError::UnsupportedFormat { .. }));
+}
+
+#[test]
+fn test_missing_file() {
+    let error = RewireConfig::from_file(fixture("missing.toml")).unwrap_err();
+
+    assert!(matches!(error, ConfigError::Io { .. }));
+}
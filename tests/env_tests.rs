@@ -0,0 +1,183 @@
+mod common;
+
+use common::test_utils::rewrite;
+use reqwest::Url;
+use reqwest_rewire::{
+    BlockingClient, BlockingRewireClient, Client, DEFAULT_ENV_PREFIX, RewireClient, RewireError,
+};
+use std::sync::{Mutex, MutexGuard};
+
+/// Serializes the tests of this file, since they all read and write the process environment.
+static ENV: Mutex<()> = Mutex::new(());
+
+/// Sets `variables` for the duration of a test, removing them when dropped.
+struct EnvVars {
+    names: Vec<String>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl EnvVars {
+    fn set(variables: &[(&str, &str)]) -> Self {
+        let lock = ENV.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for (name, value) in variables {
+            // SAFETY: every test touching the environment holds the `ENV` lock.
+            unsafe { std::env::set_var(name, value) };
+        }
+        Self {
+            names: variables.iter().map(|(name, _)| name.to_string()).collect(),
+            _lock: lock,
+        }
+    }
+}
+
+impl Drop for EnvVars {
+    fn drop(&mut self) {
+        for name in &self.names {
+            // SAFETY: the `ENV` lock is still held.
+            unsafe { std::env::remove_var(name) };
+        }
+    }
+}
+
+#[test]
+fn test_from_env_indexed_variables() {
+    let _env = EnvVars::set(&[
+        ("INDEXED_REWIRE_0", "https://api.com/->http://mock:3000/"),
+        (
+            "INDEXED_REWIRE_1",
+            " https://auth.api.com/ -> http://mock:3001/ ",
+        ),
+    ]);
+
+    let client = RewireClient::from_env("INDEXED_REWIRE").unwrap();
+
+    assert_eq!(
+        rewrite(client.rules(), "https://api.com/users").as_deref(),
+        Some("http://mock:3000/users")
+    );
+    assert_eq!(
+        rewrite(client.rules(), "https://auth.api.com/login").as_deref(),
+        Some("http://mock:3001/login")
+    );
+}
+
+#[test]
+fn test_from_env_rules_list() {
+    let _env = EnvVars::set(&[(
+        "LIST_REWIRE_RULES",
+        "https://api.com/->http://mock:3000/, https://auth.api.com/ -> http://mock:3001/\n",
+    )]);
+
+    let client = RewireClient::from_env("LIST_REWIRE").unwrap();
+
+    assert_eq!(
+        rewrite(client.rules(), "https://api.com/users").as_deref(),
+        Some("http://mock:3000/users")
+    );
+    assert_eq!(
+        rewrite(client.rules(), "https://auth.api.com/login").as_deref(),
+        Some("http://mock:3001/login")
+    );
+}
+
+#[test]
+fn test_from_env_indexed_variables_follow_numeric_order() {
+    // The three sources match the same requests, so the first duplicate reports the order.
+    let _env = EnvVars::set(&[
+        ("ORDER_REWIRE_RULES", "https://api.com/->http://mock:3000/"),
+        ("ORDER_REWIRE_10", "https://api.com:443/->http://mock:3010/"),
+        ("ORDER_REWIRE_2", "https://api.com->http://mock:3002/"),
+    ]);
+
+    let error = RewireClient::from_env("ORDER_REWIRE").err().unwrap();
+
+    assert_eq!(
+        error,
+        RewireError::DuplicateRule {
+            source: "https://api.com".to_string(),
+            existing: "https://api.com/".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_from_env_ignores_other_variables() {
+    let _env = EnvVars::set(&[
+        ("OTHER_REWIRE_0", "https://api.com/->http://mock:3000/"),
+        ("OTHER_REWIRE_TIMEOUT", "30"),
+        ("OTHER_REWIRED_0", "not a rule"),
+    ]);
+
+    let client = RewireClient::from_env("OTHER_REWIRE").unwrap();
+
+    assert_eq!(
+        rewrite(client.rules(), "https://api.com/users").as_deref(),
+        Some("http://mock:3000/users")
+    );
+}
+
+#[test]
+fn test_from_env_rejects_malformed_entry() {
+    let _env = EnvVars::set(&[("MALFORMED_REWIRE_0", "https://api.com/ http://mock:3000/")]);
+
+    let error = RewireClient::from_env("MALFORMED_REWIRE").err().unwrap();
+
+    assert_eq!(
+        error,
+        RewireError::InvalidEnvRule {
+            variable: "MALFORMED_REWIRE_0".to_string(),
+            value: "https://api.com/ http://mock:3000/".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_from_env_rejects_invalid_rule() {
+    let _env = EnvVars::set(&[("INVALID_REWIRE_RULES", "https://api.com/->not a url")]);
+
+    let error = RewireClient::from_env("INVALID_REWIRE").err().unwrap();
+
+    assert!(matches!(error, RewireError::InvalidTarget { .. }));
+}
+
+#[test]
+fn test_blocking_from_env() {
+    let _env = EnvVars::set(&[("BLOCKING_REWIRE_0", "https://api.com/->http://mock:3000/")]);
+
+    let client = BlockingRewireClient::from_env("BLOCKING_REWIRE").unwrap();
+    let url = Url::parse("https://api.com/users").unwrap();
+
+    assert_eq!(
+        client.rules().rewrite(&url).unwrap().as_str(),
+        "http://mock:3000/users"
+    );
+}
+
+#[test]
+fn test_client_from_env_without_rules() {
+    let _env = EnvVars::set(&[]);
+
+    assert!(matches!(Client::from_env(), Ok(Client::ReqwestClient(_))));
+    assert!(matches!(
+        BlockingClient::from_env(),
+        Ok(BlockingClient::ReqwestClient(_))
+    ));
+}
+
+#[test]
+fn test_client_from_env_with_rules() {
+    let variable = format!("{}_RULES", DEFAULT_ENV_PREFIX);
+    let _env = EnvVars::set(&[(&variable, "https://api.com/->http://mock:3000/")]);
+
+    let Ok(Client::TestClient(client)) = Client::from_env() else {
+        panic!("expected a test client");
+    };
+    assert_eq!(
+        rewrite(client.rules(), "https://api.com/users").as_deref(),
+        Some("http://mock:3000/users")
+    );
+    assert!(matches!(
+        BlockingClient::from_env(),
+        Ok(BlockingClient::TestClient(_))
+    ));
+}
@@ -0,0 +1,293 @@
+mod common;
+
+use common::test_utils::*;
+use httpmock::prelude::*;
+use reqwest::{Request, Url};
+use reqwest_rewire::{
+    BlockingRewireClient, BlockingTestableClientExt, Client, RewireClient, TestableClient,
+    TestableClientExt, UnmatchedPolicy,
+};
+
+fn build_request(server: &MockServer, method: Method, path: &str) -> Request {
+    reqwest::Client::new()
+        .request(method.to_string().parse().unwrap(), server.url(path))
+        .body("This is a string body")
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_execute_built_request_rewire() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rewire_request/nested_path")
+            .body("This is a string body");
+        then.status(200);
+    });
+    let client = RewireClient::new(build_rewire_test_params(&server));
+
+    let request = build_request(&server, POST, "/request/nested_path");
+    let response = client.execute(request).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_execute_manual_request_rewire() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/rewire_request");
+        then.status(200);
+    });
+    let client = RewireClient::new(build_rewire_test_params(&server));
+
+    let url = Url::parse(&server.url("/request")).unwrap();
+    let response = client
+        .execute(Request::new(http::Method::GET, url))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_execute_request_builder_build_rewire() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(PUT).path("/rewire_request");
+        then.status(200);
+    });
+    let client = RewireClient::new(build_rewire_test_params(&server));
+
+    let request = client.put(server.url("/request")).build().unwrap();
+    let response = client.execute(request).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_execute_rewires_built_request_once() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/rewire_request");
+        then.status(200);
+    });
+    let client = RewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .unmatched(UnmatchedPolicy::Error)
+        .journal(true)
+        .build()
+        .unwrap();
+
+    let request = client.get(server.url("/request")).build().unwrap();
+    let response = client.execute(request).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+    let entries = client.journal().unwrap().entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].original_url.as_str(), server.url("/request"));
+    assert!(entries[0].error.is_none());
+}
+
+#[test]
+fn test_blocking_execute_rewires_built_request_once() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/rewire_request");
+        then.status(200);
+    });
+    let client = BlockingRewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .unmatched(UnmatchedPolicy::Error)
+        .journal(true)
+        .build()
+        .unwrap();
+
+    let request = client.get(server.url("/request")).build().unwrap();
+    let response = client.execute(request).unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+    assert_eq!(client.journal().unwrap().entries().len(), 1);
+}
+
+#[tokio::test]
+async fn test_built_request_is_rewired_for_other_clients() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/mock/users");
+        then.status(200);
+    });
+    let client = RewireClient::builder()
+        .rewire("https://real-api.com/api/", server.url("/mock/"))
+        .build()
+        .unwrap();
+
+    let request = client
+        .get("https://real-api.com/api/users")
+        .build()
+        .unwrap();
+    assert_eq!(request.url().as_str(), server.url("/mock/users"));
+    let response = reqwest::Client::new().execute(request).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[test]
+fn test_blocking_built_request_is_rewired_for_other_clients() {
+    let client = BlockingRewireClient::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/mock/")
+        .build()
+        .unwrap();
+
+    let request = client
+        .get("https://real-api.com/api/users")
+        .build()
+        .unwrap();
+
+    assert_eq!(request.url().as_str(), "http://localhost:3000/mock/users");
+}
+
+#[tokio::test]
+async fn test_build_split_rewires_the_request() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/mock/users")
+            .query_param("page", "2");
+        then.status(200);
+    });
+    let client = RewireClient::builder()
+        .rewire("https://real-api.com/api/", server.url("/mock/"))
+        .build()
+        .unwrap();
+
+    let (reqwest_client, request) = client
+        .get("https://real-api.com/api/users")
+        .query(&[("page", "2")])
+        .build_split();
+    let response = reqwest_client.execute(request.unwrap()).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[test]
+fn test_blocking_build_split_rewires_the_request() {
+    let client = BlockingRewireClient::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/mock/")
+        .build()
+        .unwrap();
+
+    let (_, request) = client
+        .get("https://real-api.com/api/users")
+        .form(&[("name", "Alice")])
+        .build_split();
+
+    assert_eq!(
+        request.unwrap().url().as_str(),
+        "http://localhost:3000/mock/users"
+    );
+}
+
+#[tokio::test]
+async fn test_execute_rewires_each_built_request_once_through_clones() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/rewire_request");
+        then.status(200);
+    });
+    let client = RewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .rewire(server.url("/rewire_request"), server.url("/chained"))
+        .journal(true)
+        .build()
+        .unwrap();
+
+    let first = client.get(server.url("/request")).build().unwrap();
+    let second = client.get(server.url("/request")).build().unwrap();
+    client.clone().execute(first).await.unwrap();
+    client.execute(second).await.unwrap();
+
+    mock.assert_calls(2);
+    assert_eq!(client.journal().unwrap().entries().len(), 2);
+}
+
+#[tokio::test]
+async fn test_execute_unmatched_request() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/other");
+        then.status(200);
+    });
+    let client = RewireClient::new(build_rewire_test_params(&server));
+
+    let response = client
+        .execute(build_request(&server, GET, "/other"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_execute_through_client_enum_rewire() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(DELETE).path("/rewire_request");
+        then.status(200);
+    });
+    let client = Client::TestClient(RewireClient::new(build_rewire_test_params(&server)));
+
+    let response = client
+        .execute(build_request(&server, DELETE, "/request"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_execute_through_trait_object_rewire() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(PATCH).path("/rewire_request");
+        then.status(200);
+    });
+    let client: Box<dyn TestableClient> =
+        Box::new(RewireClient::new(build_rewire_test_params(&server)));
+
+    let response = client
+        .execute(build_request(&server, PATCH, "/request"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_execute_through_reqwest_client() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/request");
+        then.status(200);
+    });
+    let client = Client::ReqwestClient(reqwest::Client::new());
+
+    let response = client
+        .execute(build_request(&server, GET, "/request"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
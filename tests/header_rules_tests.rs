@@ -0,0 +1,443 @@
+use httpmock::prelude::*;
+use reqwest::header::{ACCEPT, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, Url};
+use reqwest_rewire::{
+    BlockingRewireClient, BlockingTestableClientExt, RewireClient, RewireError, RewireRules,
+    TestableClientExt,
+};
+
+const V2: &str = "application/vnd.v2+json";
+
+fn rewrite(
+    rules: &RewireRules,
+    method: Method,
+    url: &str,
+    headers: &[(&str, &str)],
+) -> Option<String> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        map.append(
+            HeaderName::from_bytes(name.as_bytes()).unwrap(),
+            HeaderValue::from_str(value).unwrap(),
+        );
+    }
+    rules
+        .rewrite_with_headers(&method, &Url::parse(url).unwrap(), &map)
+        .map(|url| url.to_string())
+}
+
+fn versioned_rules() -> RewireRules {
+    RewireRules::builder()
+        .rewire("https://api.com/", "http://localhost:3001/")
+        .rewire_headers(
+            [("accept", V2)],
+            "https://api.com/",
+            "http://localhost:3002/",
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_header_rule_applies_to_requests_carrying_the_header() {
+    let rules = versioned_rules();
+
+    assert_eq!(
+        rewrite(
+            &rules,
+            Method::GET,
+            "https://api.com/users",
+            &[("accept", V2)]
+        ),
+        Some("http://localhost:3002/users".to_string())
+    );
+    assert_eq!(
+        rewrite(
+            &rules,
+            Method::GET,
+            "https://api.com/users",
+            &[("accept", "application/json")]
+        ),
+        Some("http://localhost:3001/users".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, Method::GET, "https://api.com/users", &[]),
+        Some("http://localhost:3001/users".to_string())
+    );
+}
+
+#[test]
+fn test_header_names_are_case_insensitive_and_values_can_be_list_items() {
+    let rules = versioned_rules();
+
+    assert_eq!(
+        rewrite(
+            &rules,
+            Method::GET,
+            "https://api.com/users",
+            &[("Accept", "text/plain, application/vnd.v2+json")]
+        ),
+        Some("http://localhost:3002/users".to_string())
+    );
+}
+
+#[test]
+fn test_rewrite_without_headers_ignores_header_rules() {
+    let rules = versioned_rules();
+    let url = Url::parse("https://api.com/users").unwrap();
+
+    assert_eq!(
+        rules.rewrite_request(&Method::GET, &url).unwrap().as_str(),
+        "http://localhost:3001/users"
+    );
+    assert_eq!(
+        rules.rewrite(&url).unwrap().as_str(),
+        "http://localhost:3001/users"
+    );
+}
+
+#[test]
+fn test_longer_rule_without_conditions_beats_header_rule() {
+    let rules = RewireRules::builder()
+        .rewire("https://api.com/v1/users", "http://localhost:3001/")
+        .rewire_headers(
+            [("x-tenant", "acme")],
+            "https://api.com/",
+            "http://localhost:3002/",
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(
+            &rules,
+            Method::GET,
+            "https://api.com/v1/users",
+            &[("x-tenant", "acme")]
+        ),
+        Some("http://localhost:3001/".to_string())
+    );
+    assert_eq!(
+        rewrite(
+            &rules,
+            Method::GET,
+            "https://api.com/v1/orders",
+            &[("x-tenant", "acme")]
+        ),
+        Some("http://localhost:3002/v1/orders".to_string())
+    );
+}
+
+#[test]
+fn test_override_of_longer_path_beats_header_rule() {
+    let rules = RewireRules::builder()
+        .rewire("https://api.com/payments", "http://localhost:3001/payments")
+        .rewire_headers([("x-tenant", "a")], "https://api.com/", "http://tenant-a/")
+        .build()
+        .unwrap();
+
+    let _guard = rules
+        .override_rule("https://api.com/payments", "http://localhost:3002/")
+        .unwrap();
+
+    assert_eq!(
+        rewrite(
+            &rules,
+            Method::GET,
+            "https://api.com/payments",
+            &[("x-tenant", "a")]
+        ),
+        Some("http://localhost:3002/".to_string())
+    );
+    assert_eq!(
+        rewrite(
+            &rules,
+            Method::GET,
+            "https://api.com/users",
+            &[("x-tenant", "a")]
+        ),
+        Some("http://tenant-a/users".to_string())
+    );
+}
+
+#[test]
+fn test_override_beats_header_rule_of_same_source() {
+    let rules = versioned_rules();
+
+    let _guard = rules
+        .override_rule("https://api.com/", "http://localhost:3003/")
+        .unwrap();
+
+    assert_eq!(
+        rewrite(
+            &rules,
+            Method::GET,
+            "https://api.com/users",
+            &[("accept", V2)]
+        ),
+        Some("http://localhost:3003/users".to_string())
+    );
+}
+
+#[test]
+fn test_rule_requiring_more_headers_wins() {
+    let rules = RewireRules::builder()
+        .rewire_headers(
+            [("accept", V2)],
+            "https://api.com/",
+            "http://localhost:3001/",
+        )
+        .rewire_headers(
+            [("accept", V2), ("x-tenant", "acme")],
+            "https://api.com/",
+            "http://localhost:3002/",
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(
+            &rules,
+            Method::GET,
+            "https://api.com/",
+            &[("accept", V2), ("x-tenant", "acme")]
+        ),
+        Some("http://localhost:3002/".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, Method::GET, "https://api.com/", &[("accept", V2)]),
+        Some("http://localhost:3001/".to_string())
+    );
+}
+
+#[test]
+fn test_tie_between_header_rules_does_not_depend_on_registration_order() {
+    let accept = ([("accept", V2)], "http://accept/");
+    let tenant = ([("x-tenant", "a")], "http://tenant/");
+    let both = [("accept", V2), ("x-tenant", "a")];
+
+    for (first, second) in [(accept, tenant), (tenant, accept)] {
+        let rules = RewireRules::builder()
+            .rewire_headers(first.0, "https://api.com/", first.1)
+            .rewire_headers(second.0, "https://api.com/", second.1)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            rewrite(&rules, Method::GET, "https://api.com/x", &both),
+            Some("http://accept/x".to_string())
+        );
+        assert_eq!(
+            rewrite(
+                &rules,
+                Method::GET,
+                "https://api.com/x",
+                &[("x-tenant", "a")]
+            ),
+            Some("http://tenant/x".to_string())
+        );
+    }
+
+    let rules = RewireRules::default();
+    rules
+        .add_header_rule([("accept", V2)], "https://api.com/", "http://accept/")
+        .unwrap();
+    rules
+        .add_header_rule([("x-tenant", "a")], "https://api.com/", "http://tenant/")
+        .unwrap();
+    rules
+        .add_header_rule([("accept", V2)], "https://api.com/", "http://accept/")
+        .unwrap();
+    assert_eq!(
+        rewrite(&rules, Method::GET, "https://api.com/x", &both),
+        Some("http://accept/x".to_string())
+    );
+}
+
+#[test]
+fn test_method_rule_beats_header_rule() {
+    let rules = RewireRules::builder()
+        .rewire_headers(
+            [("accept", V2)],
+            "https://api.com/",
+            "http://localhost:3001/",
+        )
+        .rewire_methods([Method::POST], "https://api.com/", "http://localhost:3002/")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(&rules, Method::POST, "https://api.com/", &[("accept", V2)]),
+        Some("http://localhost:3002/".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, Method::GET, "https://api.com/", &[("accept", V2)]),
+        Some("http://localhost:3001/".to_string())
+    );
+}
+
+#[test]
+fn test_rules_requiring_the_same_headers_are_duplicates() {
+    let error = RewireRules::builder()
+        .rewire_headers(
+            [("accept", V2), ("x-tenant", "acme")],
+            "https://api.com/",
+            "http://a/",
+        )
+        .rewire_headers(
+            [("X-Tenant", "acme"), ("accept", V2)],
+            "https://api.com",
+            "http://b/",
+        )
+        .build()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        RewireError::DuplicateRule {
+            source: "https://api.com [x-tenant: acme, accept: application/vnd.v2+json]".to_string(),
+            existing: "https://api.com/ [accept: application/vnd.v2+json, x-tenant: acme]"
+                .to_string()
+        }
+    );
+}
+
+#[test]
+fn test_invalid_header_is_rejected() {
+    let error = RewireRules::builder()
+        .rewire_headers(
+            [("bad header", "v")],
+            "https://api.com/",
+            "http://localhost:3000/",
+        )
+        .build()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        RewireError::InvalidHeader {
+            name: "bad header".to_string(),
+            value: "v".to_string()
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "invalid header condition `bad header: v`"
+    );
+}
+
+#[test]
+fn test_header_rules_can_be_added_and_removed() {
+    let rules = RewireRules::default();
+
+    rules
+        .add_header_rule(
+            [("accept", V2)],
+            "https://api.com/",
+            "http://localhost:3002/",
+        )
+        .unwrap();
+    assert_eq!(
+        rewrite(&rules, Method::GET, "https://api.com/", &[("accept", V2)]),
+        Some("http://localhost:3002/".to_string())
+    );
+
+    assert!(!rules.remove_header_rule([("accept", "application/json")], "https://api.com/"));
+    assert!(rules.remove_header_rule([("Accept", V2)], "https://api.com/"));
+    assert_eq!(
+        rewrite(&rules, Method::GET, "https://api.com/", &[("accept", V2)]),
+        None
+    );
+}
+
+fn versioned_client(server: &MockServer) -> RewireClient {
+    RewireClient::builder()
+        .rewire("https://api.com/", server.url("/v1/"))
+        .rewire_headers([("accept", V2)], "https://api.com/", server.url("/v2/"))
+        .journal(true)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_execute_matches_on_request_headers() {
+    let server = MockServer::start();
+    let v1 = server.mock(|when, then| {
+        when.method(GET).path("/v1/users");
+        then.status(200);
+    });
+    let v2 = server.mock(|when, then| {
+        when.method(GET).path("/v2/users").header("accept", V2);
+        then.status(200);
+    });
+    let client = versioned_client(&server);
+
+    let mut request = reqwest::Request::new(Method::GET, "https://api.com/users".parse().unwrap());
+    request
+        .headers_mut()
+        .insert(ACCEPT, HeaderValue::from_static(V2));
+    client.execute(request).await.unwrap();
+    let request = reqwest::Request::new(Method::GET, "https://api.com/users".parse().unwrap());
+    client.execute(request).await.unwrap();
+
+    v1.assert();
+    v2.assert();
+    assert_eq!(
+        client.journal().unwrap().entries()[0].rule.as_deref(),
+        Some("https://api.com/ [accept: application/vnd.v2+json]")
+    );
+}
+
+#[tokio::test]
+async fn test_request_methods_match_on_headers_set_on_the_builder() {
+    let server = MockServer::start();
+    let v1 = server.mock(|when, then| {
+        when.method(GET).path("/v1/users");
+        then.status(200);
+    });
+    let v2 = server.mock(|when, then| {
+        when.method(GET).path("/v2/users");
+        then.status(200);
+    });
+    let client = versioned_client(&server);
+
+    client
+        .get("https://api.com/users")
+        .header(ACCEPT, V2)
+        .send()
+        .await
+        .unwrap();
+    client.get("https://api.com/users").send().await.unwrap();
+
+    v1.assert();
+    v2.assert();
+    assert_eq!(client.journal().unwrap().entries().len(), 2);
+}
+
+#[test]
+fn test_blocking_request_methods_match_on_headers_set_on_the_builder() {
+    let server = MockServer::start();
+    let tenant = server.mock(|when, then| {
+        when.method(POST).path("/acme/orders");
+        then.status(201);
+    });
+    let client = BlockingRewireClient::builder()
+        .rewire_headers(
+            [("x-tenant", "acme")],
+            "https://api.com/",
+            server.url("/acme/"),
+        )
+        .build()
+        .unwrap();
+
+    let response = client
+        .post("https://api.com/orders")
+        .header("x-tenant", "acme")
+        .send()
+        .unwrap();
+
+    assert_eq!(response.status(), 201);
+    tenant.assert();
+}
@@ -0,0 +1,249 @@
+use reqwest_rewire::{
+    BlockingRewireClient, BlockingTestableClientExt, Error, RewireClient, RewireClientBuilder,
+    RewireError, TestableClientExt, UnmatchedPolicy,
+};
+
+/// The URL `client` sends a GET request to `url` to, or the message of the error refusing it.
+fn destination(client: &RewireClient, url: &str) -> Result<String, String> {
+    client
+        .get(url)
+        .build()
+        .map(|request| request.url().to_string())
+        .map_err(|error| error.to_string())
+}
+
+fn blocked(url: &str, pattern: &str) -> String {
+    RewireError::Blocked {
+        url: url.to_string(),
+        pattern: pattern.to_string(),
+    }
+    .to_string()
+}
+
+fn unmatched(url: &str) -> String {
+    RewireError::Unmatched {
+        url: url.to_string(),
+    }
+    .to_string()
+}
+
+fn build_client(builder: RewireClientBuilder) -> RewireClient {
+    builder
+        .rewire(
+            "https://real-api.com/api/",
+            "http://localhost:3000/api-mock/",
+        )
+        .unmatched(UnmatchedPolicy::Error)
+        .allow_host("localhost")
+        .allow_host("127.0.0.0/8")
+        .deny_host("*.prod.example.com")
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_allowed_hosts_pass_through_untouched() {
+    let client = build_client(RewireClient::builder());
+
+    assert_eq!(
+        destination(&client, "http://localhost:8080/health"),
+        Ok("http://localhost:8080/health".to_string())
+    );
+    assert_eq!(
+        destination(&client, "http://127.42.0.1/health"),
+        Ok("http://127.42.0.1/health".to_string())
+    );
+    assert_eq!(
+        destination(&client, "http://128.0.0.1/health"),
+        Err(unmatched("http://128.0.0.1/health"))
+    );
+}
+
+#[test]
+fn test_denied_hosts_are_blocked() {
+    let client = build_client(RewireClient::builder());
+
+    assert_eq!(
+        destination(&client, "https://api.prod.example.com/users"),
+        Err(blocked(
+            "https://api.prod.example.com/users",
+            "*.prod.example.com"
+        ))
+    );
+    assert_eq!(
+        destination(&client, "https://a.b.PROD.example.com/"),
+        Err(blocked(
+            "https://a.b.prod.example.com/",
+            "*.prod.example.com"
+        ))
+    );
+    assert_eq!(
+        destination(&client, "https://prod.example.com/"),
+        Err(unmatched("https://prod.example.com/"))
+    );
+}
+
+#[test]
+fn test_rest_is_rewired() {
+    let client = build_client(RewireClient::builder());
+
+    assert_eq!(
+        destination(&client, "https://real-api.com/api/users"),
+        Ok("http://localhost:3000/api-mock/users".to_string())
+    );
+}
+
+#[test]
+fn test_denied_hosts_are_blocked_whatever_the_unmatched_policy() {
+    let client = RewireClient::builder()
+        .deny_host("prod.example.com")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        destination(&client, "https://prod.example.com/"),
+        Err(blocked("https://prod.example.com/", "prod.example.com"))
+    );
+    assert_eq!(
+        destination(&client, "https://staging.example.com/"),
+        Ok("https://staging.example.com/".to_string())
+    );
+}
+
+#[test]
+fn test_deny_takes_precedence_over_allow() {
+    let client = RewireClient::builder()
+        .unmatched(UnmatchedPolicy::Error)
+        .allow_host("*.example.com")
+        .deny_host("*.prod.example.com")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        destination(&client, "https://api.staging.example.com/"),
+        Ok("https://api.staging.example.com/".to_string())
+    );
+    assert_eq!(
+        destination(&client, "https://api.prod.example.com/"),
+        Err(blocked(
+            "https://api.prod.example.com/",
+            "*.prod.example.com"
+        ))
+    );
+}
+
+#[test]
+fn test_denied_hosts_are_checked_after_rewiring() {
+    let client = RewireClient::builder()
+        .rewire("https://api.prod.example.com/", "http://localhost:3000/")
+        .rewire(
+            "https://staging.example.com/",
+            "https://api.prod.example.com/",
+        )
+        .deny_host("*.prod.example.com")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        destination(&client, "https://api.prod.example.com/users"),
+        Ok("http://localhost:3000/users".to_string())
+    );
+    assert_eq!(
+        destination(&client, "https://staging.example.com/users"),
+        Err(blocked(
+            "https://api.prod.example.com/users",
+            "*.prod.example.com"
+        ))
+    );
+}
+
+#[test]
+fn test_ip_patterns() {
+    let client = RewireClient::builder()
+        .unmatched(UnmatchedPolicy::Error)
+        .allow_host("::1")
+        .allow_host("fd00::/8")
+        .allow_host("10.0.0.1")
+        .deny_host("[fd00::bad]")
+        .build()
+        .unwrap();
+
+    assert!(destination(&client, "http://[::1]:8080/").is_ok());
+    assert!(destination(&client, "http://[fd12:3456::1]/").is_ok());
+    assert!(destination(&client, "http://10.0.0.1/").is_ok());
+    assert_eq!(
+        destination(&client, "http://10.0.0.2/"),
+        Err(unmatched("http://10.0.0.2/"))
+    );
+    assert_eq!(
+        destination(&client, "http://[fd00::bad]/"),
+        Err(blocked("http://[fd00::bad]/", "[fd00::bad]"))
+    );
+}
+
+#[test]
+fn test_invalid_host_patterns() {
+    for pattern in [
+        "",
+        "*.",
+        "127.0.0.0/33",
+        "::/129",
+        "localhost/8",
+        "a*b.com",
+        "host:80",
+    ] {
+        let error = RewireClient::builder()
+            .allow_host(pattern)
+            .build()
+            .err()
+            .unwrap();
+        assert_eq!(
+            error,
+            RewireError::InvalidHostPattern {
+                pattern: pattern.to_string()
+            }
+        );
+    }
+
+    let error = RewireClient::builder()
+        .deny_host("*")
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(error, RewireError::InvalidHostPattern { .. }));
+}
+
+#[test]
+fn test_blocked_request_does_not_panic_in_panic_mode() {
+    let client = RewireClient::builder()
+        .unmatched(UnmatchedPolicy::Panic)
+        .deny_host("prod.example.com")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        destination(&client, "https://prod.example.com/"),
+        Err(blocked("https://prod.example.com/", "prod.example.com"))
+    );
+}
+
+#[test]
+fn test_blocking_client_denied_hosts() {
+    let client = BlockingRewireClient::builder()
+        .deny_host("*.prod.example.com")
+        .build()
+        .unwrap();
+
+    let error = client
+        .get("https://api.prod.example.com/")
+        .send()
+        .unwrap_err();
+
+    let Error::Rewire(error) = error else {
+        panic!("expected a rewire error, got {}", error);
+    };
+    assert_eq!(
+        error.to_string(),
+        blocked("https://api.prod.example.com/", "*.prod.example.com")
+    );
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use common::test_utils::*;
+use httpmock::MockServer;
+use reqwest::Url;
+use reqwest_rewire::{Client, RewireClient, TestableClient, TestableClientExt};
+
+fn rewire_client() -> RewireClient {
+    RewireClient::builder()
+        .rewire(
+            "https://api.example.com/api/",
+            "http://localhost:3000/mock/",
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_request_with_str() {
+    assert_eq!(
+        rewired_url(&rewire_client(), "https://api.example.com/api/users"),
+        "http://localhost:3000/mock/users"
+    );
+}
+
+#[test]
+fn test_request_with_string() {
+    let url = String::from("https://api.example.com/api/users");
+    let client = rewire_client();
+    assert_eq!(
+        client.post(&url).build().unwrap().url().as_str(),
+        "http://localhost:3000/mock/users"
+    );
+    assert_eq!(
+        client.put(url).build().unwrap().url().as_str(),
+        "http://localhost:3000/mock/users"
+    );
+}
+
+#[test]
+fn test_request_with_url() {
+    let url = Url::parse("https://api.example.com/api/users?id=42").unwrap();
+    let client = rewire_client();
+    let request = client.request(http::Method::PATCH, url).build().unwrap();
+    assert_eq!(request.method(), http::Method::PATCH);
+    assert_eq!(
+        request.url().as_str(),
+        "http://localhost:3000/mock/users?id=42"
+    );
+}
+
+#[test]
+fn test_request_with_invalid_url_fails_when_built() {
+    let client = rewire_client();
+    assert!(client.get("not a url").build().is_err());
+    assert!(client.get(String::new()).build().is_err());
+}
+
+#[test]
+fn test_request_through_trait_objects() {
+    let boxed: Box<dyn TestableClient> = Box::new(rewire_client());
+    let shared: Box<dyn TestableClient + Send + Sync> = Box::new(rewire_client());
+    let borrowed: &dyn TestableClient = &rewire_client();
+    let url = Url::parse("https://api.example.com/api/users").unwrap();
+
+    for request in [
+        boxed.get(url.clone()).build().unwrap(),
+        shared.delete(url.as_str()).build().unwrap(),
+        borrowed.head(url.to_string()).build().unwrap(),
+    ] {
+        assert_eq!(
+            request.url().as_str(),
+            "http://localhost:3000/mock/users"
+        );
+    }
+}
+
+#[test]
+fn test_request_through_client_enum() {
+    let url = Url::parse("https://api.example.com/api/users").unwrap();
+
+    let client = Client::TestClient(rewire_client());
+    assert_eq!(
+        rewired_url(&client, url.as_str()),
+        "http://localhost:3000/mock/users"
+    );
+
+    let client = Client::ReqwestClient(reqwest::Client::new());
+    assert_eq!(
+        rewired_url(&client, url.as_str()),
+        "https://api.example.com/api/users"
+    );
+}
+
+#[tokio::test]
+async fn test_get_request_through_trait_object_rewire() {
+    let server = MockServer::start();
+    let client: Box<dyn TestableClient> =
+        Box::new(RewireClient::new(build_rewire_test_params(&server)));
+    let mock = server.mock(|when, then| {
+        when.path("/rewire_request/nested_path");
+        then.status(200);
+    });
+
+    let url = Url::parse(&server.url("/request/nested_path")).unwrap();
+    let response = client.get(url).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
@@ -0,0 +1,179 @@
+use httpmock::prelude::*;
+use reqwest_rewire::{
+    BlockingRewireClient, BlockingTestableClientExt, RewireClient, TestableClientExt, times,
+};
+use std::panic::{self, AssertUnwindSafe};
+
+fn build_client(server: &MockServer) -> RewireClient {
+    RewireClient::builder()
+        .rewire("https://real-api.com/api/", server.url("/api-mock"))
+        .rewire("https://auth.real-api.com/", server.url("/auth-mock"))
+        .journal(true)
+        .build()
+        .unwrap()
+}
+
+fn mock_all(server: &MockServer) {
+    server.mock(|when, then| {
+        when.any_request();
+        then.status(200);
+    });
+}
+
+fn panic_message(assertion: impl FnOnce()) -> String {
+    let payload = panic::catch_unwind(AssertUnwindSafe(assertion)).unwrap_err();
+    payload.downcast_ref::<String>().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_assert_rewired_counts_requests() {
+    let server = MockServer::start();
+    mock_all(&server);
+    let client = build_client(&server);
+
+    client
+        .get("https://real-api.com/api/users")
+        .send()
+        .await
+        .unwrap();
+    client
+        .get("https://real-api.com/api/users")
+        .send()
+        .await
+        .unwrap();
+
+    client.assert_rewired("https://real-api.com/api/users", times(2));
+    client.assert_rewired("https://real-api.com/api/orders", times(0));
+}
+
+#[tokio::test]
+async fn test_assert_rewired_panics_with_observed_traffic() {
+    let server = MockServer::start();
+    mock_all(&server);
+    let client = build_client(&server);
+    client
+        .get("https://real-api.com/api/users")
+        .send()
+        .await
+        .unwrap();
+    client
+        .get("https://auth.real-api.com/token")
+        .send()
+        .await
+        .unwrap();
+
+    let message =
+        panic_message(|| client.assert_rewired("https://real-api.com/api/users", times(2)));
+
+    assert_eq!(
+        message,
+        format!(
+            "expected `https://real-api.com/api/users` to be rewired 2 times, but it was rewired \
+             once\n\nobserved traffic:\n  \
+             > GET https://real-api.com/api/users -> {} (rule `https://real-api.com/api/`)\n    \
+             GET https://auth.real-api.com/token -> {} (rule `https://auth.real-api.com/`)",
+            server.url("/api-mock/users"),
+            server.url("/auth-mock/token")
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_assert_not_called_checks_destinations() {
+    let server = MockServer::start();
+    mock_all(&server);
+    let client = build_client(&server);
+
+    client
+        .get("https://real-api.com/api/users")
+        .send()
+        .await
+        .unwrap();
+
+    client.assert_not_called("real-api.com");
+    client.assert_not_called("*.real-api.com");
+    let message = panic_message(|| client.assert_not_called("127.0.0.1"));
+    assert!(
+        message.starts_with("expected no request to `127.0.0.1`, but it was called once"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid host pattern `http://real-api.com/`")]
+fn test_assert_not_called_rejects_invalid_pattern() {
+    let server = MockServer::start();
+
+    build_client(&server).assert_not_called("http://real-api.com/");
+}
+
+#[tokio::test]
+async fn test_assert_all_rules_used() {
+    let server = MockServer::start();
+    mock_all(&server);
+    let client = build_client(&server);
+    client
+        .get("https://real-api.com/api/users")
+        .send()
+        .await
+        .unwrap();
+
+    let message = panic_message(|| client.assert_all_rules_used());
+
+    assert!(
+        message.starts_with(
+            "expected every rule to be used, but these never matched:\n    \
+             https://auth.real-api.com/\n\nobserved traffic:"
+        ),
+        "{}",
+        message
+    );
+    client
+        .get("https://auth.real-api.com/token")
+        .send()
+        .await
+        .unwrap();
+    client.assert_all_rules_used();
+}
+
+#[test]
+fn test_assert_all_rules_used_lists_overridden_rule_once() {
+    let client = RewireClient::builder()
+        .rewire("http://real-api.com/", "http://localhost:3000/")
+        .journal(true)
+        .build()
+        .unwrap();
+    let _guard = client
+        .override_rule("http://real-api.com/", "http://localhost:3500/")
+        .unwrap();
+
+    let message = panic_message(|| client.assert_all_rules_used());
+
+    assert!(message.contains("never matched:\n    http://real-api.com/\n\nobserved traffic: none"));
+}
+
+#[test]
+#[should_panic(expected = "the journal is disabled")]
+fn test_assertions_require_the_journal() {
+    let client = RewireClient::builder().build().unwrap();
+
+    client.assert_all_rules_used();
+}
+
+#[test]
+fn test_blocking_client_assertions() {
+    let server = MockServer::start();
+    mock_all(&server);
+    let client = BlockingRewireClient::builder()
+        .rewire("https://real-api.com/api/", server.url("/api-mock"))
+        .journal(true)
+        .build()
+        .unwrap();
+
+    client.get("https://real-api.com/api/users").send().unwrap();
+
+    client.assert_rewired("https://real-api.com/api/users", times(1));
+    client.assert_not_called("real-api.com");
+    client.assert_all_rules_used();
+}
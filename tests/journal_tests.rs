@@ -0,0 +1,171 @@
+use httpmock::prelude::*;
+use reqwest::Method;
+use reqwest_rewire::{
+    BlockingRewireClient, BlockingTestableClientExt, RewireClient, RewireError, TestableClientExt,
+    UnmatchedPolicy,
+};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::SystemTime;
+
+fn build_client(server: &MockServer) -> RewireClient {
+    RewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .journal(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_journal_is_disabled_by_default() {
+    let client = RewireClient::builder().build().unwrap();
+
+    assert!(client.journal().is_none());
+}
+
+#[tokio::test]
+async fn test_journal_records_rewired_request() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path("/rewire_request/users");
+        then.status(200);
+    });
+    let client = build_client(&server);
+    let before = SystemTime::now();
+
+    client
+        .post(server.url("/request/users"))
+        .send()
+        .await
+        .unwrap();
+
+    let entries = client.journal().unwrap().entries();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.method, Method::POST);
+    assert_eq!(entry.original_url.as_str(), server.url("/request/users"));
+    assert_eq!(
+        entry.rewired_url.as_ref().unwrap().as_str(),
+        server.url("/rewire_request/users")
+    );
+    assert_eq!(entry.rule.as_deref(), Some(server.url("/request").as_str()));
+    assert_eq!(entry.error, None);
+    assert!(entry.timestamp >= before);
+    assert_eq!(entry.destination(), entry.rewired_url.as_ref());
+}
+
+#[tokio::test]
+async fn test_journal_records_unmatched_request() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path("/unmatched");
+        then.status(200);
+    });
+    let client = build_client(&server);
+
+    client.get(server.url("/unmatched")).send().await.unwrap();
+
+    let entry = &client.journal().unwrap().entries()[0];
+    assert_eq!(entry.method, Method::GET);
+    assert_eq!(entry.rewired_url, None);
+    assert_eq!(entry.rule, None);
+    assert_eq!(entry.destination(), Some(&entry.original_url));
+}
+
+#[tokio::test]
+async fn test_journal_records_refused_request() {
+    let server = MockServer::start();
+    let client = RewireClient::builder()
+        .unmatched(UnmatchedPolicy::Error)
+        .journal(true)
+        .build()
+        .unwrap();
+
+    client
+        .get(server.url("/unmatched"))
+        .send()
+        .await
+        .unwrap_err();
+
+    let entry = &client.journal().unwrap().entries()[0];
+    assert_eq!(
+        entry.error,
+        Some(RewireError::Unmatched {
+            url: server.url("/unmatched")
+        })
+    );
+    assert_eq!(entry.destination(), None);
+}
+
+#[test]
+fn test_journal_records_request_refused_with_panic() {
+    let client = RewireClient::builder()
+        .unmatched(UnmatchedPolicy::Panic)
+        .journal(true)
+        .build()
+        .unwrap();
+    let journal = client.journal().unwrap().clone();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        client.get("http://unmatched.com/").build()
+    }));
+
+    assert!(result.is_err());
+    assert!(journal.entries()[0].error.is_some());
+}
+
+#[tokio::test]
+async fn test_journal_is_shared_across_clones() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path("/rewire_request");
+        then.status(200);
+    });
+    let client = build_client(&server);
+    let clone = client.clone();
+
+    client.get(server.url("/request")).send().await.unwrap();
+    clone.get(server.url("/request")).send().await.unwrap();
+
+    assert_eq!(client.journal().unwrap().len(), 2);
+    assert_eq!(clone.journal().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_journal_can_be_cleared() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path("/rewire_request");
+        then.status(200);
+    });
+    let client = build_client(&server);
+    client.get(server.url("/request")).send().await.unwrap();
+
+    client.journal().unwrap().clear();
+
+    assert!(client.journal().unwrap().is_empty());
+    client.get(server.url("/request")).send().await.unwrap();
+    assert_eq!(client.journal().unwrap().len(), 1);
+}
+
+#[test]
+fn test_blocking_journal_records_requests() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path("/rewire_request");
+        then.status(200);
+    });
+    let client = BlockingRewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .journal(true)
+        .build()
+        .unwrap();
+
+    client.delete(server.url("/request")).send().unwrap();
+
+    let entries = client.journal().unwrap().entries();
+    assert_eq!(entries[0].method, Method::DELETE);
+    assert_eq!(
+        entries[0].rewired_url.as_ref().unwrap().as_str(),
+        server.url("/rewire_request")
+    );
+}
@@ -0,0 +1,261 @@
+use httpmock::prelude::*;
+use reqwest::{Method, Url};
+use reqwest_rewire::{
+    BlockingRewireClient, BlockingTestableClientExt, RewireClient, RewireError, RewireRules,
+    TestableClientExt, times,
+};
+
+fn rewrite(rules: &RewireRules, method: Method, url: &str) -> Option<String> {
+    rules
+        .rewrite_request(&method, &Url::parse(url).unwrap())
+        .map(|url| url.to_string())
+}
+
+fn payment_rules() -> RewireRules {
+    RewireRules::builder()
+        .rewire("https://api.com/payments", "http://localhost:3000/payments")
+        .rewire_methods(
+            [Method::POST, Method::PUT],
+            "https://api.com/payments",
+            "http://localhost:3001/fail",
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_method_rule_only_applies_to_its_methods() {
+    let rules = payment_rules();
+
+    assert_eq!(
+        rewrite(&rules, Method::POST, "https://api.com/payments"),
+        Some("http://localhost:3001/fail".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, Method::PUT, "https://api.com/payments"),
+        Some("http://localhost:3001/fail".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, Method::GET, "https://api.com/payments"),
+        Some("http://localhost:3000/payments".to_string())
+    );
+}
+
+#[test]
+fn test_method_rule_without_fallback_leaves_other_methods_unmatched() {
+    let rules = RewireRules::builder()
+        .rewire_methods(
+            [Method::DELETE],
+            "https://api.com/",
+            "http://localhost:3000/",
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(&rules, Method::DELETE, "https://api.com/users/42"),
+        Some("http://localhost:3000/users/42".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, Method::GET, "https://api.com/users/42"),
+        None
+    );
+}
+
+#[test]
+fn test_longer_rule_for_any_method_beats_method_rule() {
+    let rules = RewireRules::builder()
+        .rewire("https://api.com/payments/refunds", "http://localhost:3000/")
+        .rewire_methods([Method::POST], "https://api.com/", "http://localhost:3001/")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(&rules, Method::POST, "https://api.com/payments/refunds/1"),
+        Some("http://localhost:3000/1".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, Method::POST, "https://api.com/users"),
+        Some("http://localhost:3001/users".to_string())
+    );
+}
+
+#[test]
+fn test_override_beats_method_rule() {
+    let rules = payment_rules();
+
+    let guard = rules
+        .override_rule("https://api.com/payments", "http://localhost:3002/")
+        .unwrap();
+    assert_eq!(
+        rewrite(&rules, Method::POST, "https://api.com/payments"),
+        Some("http://localhost:3002/".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, Method::GET, "https://api.com/payments"),
+        Some("http://localhost:3002/".to_string())
+    );
+
+    drop(guard);
+    assert_eq!(
+        rewrite(&rules, Method::POST, "https://api.com/payments"),
+        Some("http://localhost:3001/fail".to_string())
+    );
+}
+
+#[test]
+fn test_longest_method_rule_wins() {
+    let rules = RewireRules::builder()
+        .rewire_methods([Method::POST], "https://api.com/", "http://localhost:3000/")
+        .rewire_methods(
+            [Method::POST],
+            "https://api.com/payments",
+            "http://localhost:3001/",
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(&rules, Method::POST, "https://api.com/payments/7"),
+        Some("http://localhost:3001/7".to_string())
+    );
+}
+
+#[test]
+fn test_rewrite_without_method_ignores_method_rules() {
+    let rules = payment_rules();
+
+    assert_eq!(
+        rules
+            .rewrite(&Url::parse("https://api.com/payments").unwrap())
+            .map(|url| url.to_string()),
+        Some("http://localhost:3000/payments".to_string())
+    );
+}
+
+#[test]
+fn test_rules_sharing_a_method_are_duplicates() {
+    let error = RewireRules::builder()
+        .rewire_methods([Method::POST], "https://api.com/payments", "http://a/")
+        .rewire_methods(
+            [Method::PUT, Method::POST],
+            "https://api.com/payments/",
+            "http://b/",
+        )
+        .build()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        RewireError::DuplicateRule {
+            source: "PUT,POST https://api.com/payments/".to_string(),
+            existing: "POST https://api.com/payments".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_method_rules_can_be_added_and_removed() {
+    let rules = payment_rules();
+
+    rules
+        .add_method_rule(
+            [Method::GET],
+            "https://api.com/payments",
+            "http://localhost:3002/",
+        )
+        .unwrap();
+    assert_eq!(
+        rewrite(&rules, Method::GET, "https://api.com/payments"),
+        Some("http://localhost:3002/".to_string())
+    );
+
+    assert!(rules.remove_method_rule([Method::GET, Method::POST], "https://api.com/payments"));
+    assert!(!rules.remove_method_rule([Method::POST], "https://api.com/payments"));
+    assert_eq!(
+        rewrite(&rules, Method::POST, "https://api.com/payments"),
+        Some("http://localhost:3000/payments".to_string())
+    );
+}
+
+#[test]
+fn test_adding_a_method_rule_replaces_rules_sharing_a_method() {
+    let rules = payment_rules();
+
+    rules
+        .add_method_rule(
+            [Method::POST],
+            "https://api.com/payments",
+            "http://localhost:3002/",
+        )
+        .unwrap();
+
+    assert_eq!(
+        rewrite(&rules, Method::POST, "https://api.com/payments"),
+        Some("http://localhost:3002/".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, Method::PUT, "https://api.com/payments"),
+        Some("http://localhost:3000/payments".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_client_routes_by_method() {
+    let server = MockServer::start();
+    let real = server.mock(|when, then| {
+        when.method(GET).path("/payments");
+        then.status(200);
+    });
+    let failure = server.mock(|when, then| {
+        when.method(POST).path("/fail");
+        then.status(500);
+    });
+    let client = RewireClient::builder()
+        .rewire("https://api.com/payments", server.url("/payments"))
+        .rewire_methods(
+            [Method::POST],
+            "https://api.com/payments",
+            server.url("/fail"),
+        )
+        .journal(true)
+        .build()
+        .unwrap();
+
+    let read = client.get("https://api.com/payments").send().await.unwrap();
+    let write = client
+        .post("https://api.com/payments")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(read.status(), 200);
+    assert_eq!(write.status(), 500);
+    real.assert();
+    failure.assert();
+    client.assert_all_rules_used();
+    client.assert_rewired("https://api.com/payments", times(2));
+    assert_eq!(
+        client.journal().unwrap().entries()[1].rule.as_deref(),
+        Some("POST https://api.com/payments")
+    );
+}
+
+#[test]
+fn test_blocking_client_routes_by_method() {
+    let server = MockServer::start();
+    let failure = server.mock(|when, then| {
+        when.method(DELETE).path("/fail");
+        then.status(500);
+    });
+    let client = BlockingRewireClient::builder()
+        .rewire_methods([Method::DELETE], "https://api.com/", server.url("/fail"))
+        .build()
+        .unwrap();
+
+    let response = client.delete("https://api.com/").send().unwrap();
+
+    assert_eq!(response.status(), 500);
+    failure.assert();
+}
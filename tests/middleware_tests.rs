@@ -0,0 +1,187 @@
+#![cfg(feature = "middleware")]
+
+mod common;
+
+use common::test_utils::*;
+use http::Extensions;
+use httpmock::prelude::*;
+use reqwest::{Request, Response};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, Middleware, Next};
+use reqwest_rewire::{RewireError, RewireMiddleware, UnmatchedPolicy, UnusedRulePolicy};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+fn build_client(middleware: RewireMiddleware) -> ClientWithMiddleware {
+    ClientBuilder::new(reqwest::Client::new())
+        .with(middleware)
+        .build()
+}
+
+#[tokio::test]
+async fn test_get_request_rewire() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/rewire_request");
+        then.status(200).body("rewire_client");
+    });
+    let client = build_client(RewireMiddleware::new(build_rewire_test_params(&server)));
+
+    let response = client.get(server.url("/request")).send().await.unwrap();
+
+    assert_eq!(response.text().await.unwrap(), "rewire_client");
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_post_request_with_nested_path_and_query_args_rewire() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rewire_request/nested_path")
+            .query_param("line", "T1")
+            .body("This is a string body");
+        then.status(200);
+    });
+    let client = build_client(RewireMiddleware::new(build_rewire_test_params(&server)));
+
+    let response = client
+        .post(server.url("/request/nested_path?line=T1"))
+        .body("This is a string body")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_get_request() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/request");
+        then.status(200);
+    });
+    let client = build_client(RewireMiddleware::new(HashMap::new()));
+
+    let response = client.get(server.url("/request")).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+/// Records the URL of every request reaching it.
+struct RecordUrls(Arc<Mutex<Vec<String>>>);
+
+#[async_trait::async_trait]
+impl Middleware for RecordUrls {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        self.0.lock().unwrap().push(req.url().to_string());
+        next.run(req, extensions).await
+    }
+}
+
+#[tokio::test]
+async fn test_later_middleware_sees_rewired_url() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/rewire_request");
+        then.status(200);
+    });
+    let urls = Arc::new(Mutex::new(Vec::new()));
+    let client = ClientBuilder::new(reqwest::Client::new())
+        .with(RewireMiddleware::new(build_rewire_test_params(&server)))
+        .with(RecordUrls(urls.clone()))
+        .build();
+
+    let response = client.get(server.url("/request")).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+    assert_eq!(*urls.lock().unwrap(), vec![server.url("/rewire_request")]);
+}
+
+#[test]
+fn test_middleware_builder_is_strict_by_default() {
+    let error = RewireMiddleware::builder()
+        .rewire("not a url", "http://localhost:3000/")
+        .build()
+        .err();
+    assert!(matches!(error, Some(RewireError::InvalidSource { .. })));
+}
+
+#[tokio::test]
+async fn test_unmatched_request_fails_with_rewire_error() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/unmatched");
+        then.status(200);
+    });
+    let middleware = RewireMiddleware::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .unmatched(UnmatchedPolicy::Error)
+        .build()
+        .unwrap();
+
+    let error = build_client(middleware)
+        .get(server.url("/unmatched"))
+        .send()
+        .await
+        .unwrap_err();
+
+    let reqwest_middleware::Error::Middleware(error) = error else {
+        panic!("expected a middleware error, got {}", error);
+    };
+    assert_eq!(
+        error.downcast_ref::<RewireError>(),
+        Some(&RewireError::Unmatched {
+            url: server.url("/unmatched")
+        })
+    );
+    mock.assert_calls(0);
+}
+
+#[tokio::test]
+async fn test_middleware_journal_records_requests() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rewire_request");
+        then.status(200);
+    });
+    let middleware = RewireMiddleware::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .journal(true)
+        .build()
+        .unwrap();
+    let journal = middleware.journal().unwrap().clone();
+
+    build_client(middleware)
+        .get(server.url("/request"))
+        .send()
+        .await
+        .unwrap();
+
+    let entries = journal.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        entries[0].rewired_url.as_ref().unwrap().as_str(),
+        server.url("/rewire_request")
+    );
+}
+
+#[test]
+#[should_panic(expected = "these rules never matched a request:")]
+fn test_middleware_reports_unused_rules_on_drop() {
+    let middleware = RewireMiddleware::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/api-mock/")
+        .unused_rules(UnusedRulePolicy::Panic)
+        .build()
+        .unwrap();
+
+    drop(build_client(middleware));
+}
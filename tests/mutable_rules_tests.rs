@@ -0,0 +1,156 @@
+mod common;
+
+use common::test_utils::rewrite;
+use httpmock::prelude::*;
+use reqwest_rewire::{
+    BlockingRewireClient, Client, RewireClient, RewireError, RewireRules, TestableClientExt,
+};
+
+#[tokio::test]
+async fn test_rule_added_after_injection_is_used_by_clones() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/rewire_request/users");
+        then.status(200).body("rewire_client");
+    });
+    let client = RewireClient::builder().build().unwrap();
+    let injected = Client::TestClient(client.clone());
+
+    client
+        .add_rule("https://real-api.com/api/", &server.url("/rewire_request/"))
+        .unwrap();
+    let response = injected
+        .get("https://real-api.com/api/users")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.text().await.unwrap(), "rewire_client");
+    mock.assert();
+}
+
+#[test]
+fn test_add_rule_replaces_equivalent_rule() {
+    let client = RewireClient::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/")
+        .build()
+        .unwrap();
+
+    client
+        .add_rule("https://real-api.com:443/api", "http://localhost:4000/")
+        .unwrap();
+
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/users").as_deref(),
+        Some("http://localhost:4000/users")
+    );
+}
+
+#[test]
+fn test_add_rule_rejects_invalid_rule() {
+    let client = RewireClient::builder().build().unwrap();
+
+    let error = client
+        .add_rule("https://real-api.com/api/", "not a url")
+        .unwrap_err();
+
+    assert!(matches!(error, RewireError::InvalidTarget { .. }));
+    assert_eq!(rewrite(client.rules(), "https://real-api.com/api/"), None);
+}
+
+#[test]
+fn test_remove_rule_falls_back_to_less_specific_rule() {
+    let client = RewireClient::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/")
+        .rewire("https://real-api.com/api/v1/", "http://localhost:3001/")
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    assert!(client.remove_rule("https://real-api.com:443/api/v1"));
+    assert!(!client.remove_rule("https://real-api.com/api/v1/"));
+
+    assert_eq!(
+        rewrite(clone.rules(), "https://real-api.com/api/v1/users").as_deref(),
+        Some("http://localhost:3000/v1/users")
+    );
+}
+
+#[test]
+fn test_remove_rule_keeps_more_specific_rules() {
+    let client = RewireClient::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/")
+        .rewire("https://real-api.com/api/v1/", "http://localhost:3001/")
+        .build()
+        .unwrap();
+
+    assert!(client.remove_rule("https://real-api.com/api/"));
+
+    assert_eq!(rewrite(client.rules(), "https://real-api.com/api/v2"), None);
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/v1/users").as_deref(),
+        Some("http://localhost:3001/users")
+    );
+}
+
+#[test]
+fn test_remove_unknown_rule() {
+    let client = RewireClient::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/")
+        .build()
+        .unwrap();
+
+    assert!(!client.remove_rule("https://real-api.com/"));
+    assert!(!client.remove_rule("https://real-api.com/api/v1"));
+    assert!(!client.remove_rule("https://other-api.com/api/"));
+    assert!(!client.remove_rule("not a url"));
+    assert!(rewrite(client.rules(), "https://real-api.com/api/").is_some());
+}
+
+#[test]
+fn test_clear_removes_rules_of_every_clone() {
+    let client = BlockingRewireClient::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/")
+        .rewire("https://auth.real-api.com/", "http://localhost:3001/")
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    clone.clear();
+
+    assert_eq!(rewrite(client.rules(), "https://real-api.com/api/"), None);
+    assert_eq!(rewrite(client.rules(), "https://auth.real-api.com/"), None);
+}
+
+#[test]
+fn test_concurrent_updates_are_atomic() {
+    let rules = RewireRules::default();
+    rules
+        .add_rule("https://real-api.com/api/", "http://localhost:3000/")
+        .unwrap();
+
+    std::thread::scope(|scope| {
+        for i in 0..4 {
+            let rules = rules.clone();
+            scope.spawn(move || {
+                for j in 0..100 {
+                    let source = format!("https://real-api.com/api/{}/{}", i, j);
+                    rules.add_rule(&source, "http://localhost:4000/").unwrap();
+                    assert!(rules.remove_rule(&source));
+                }
+            });
+        }
+        let rules = rules.clone();
+        scope.spawn(move || {
+            for _ in 0..400 {
+                let rewired = rewrite(&rules, "https://real-api.com/api/users");
+                assert_eq!(rewired.as_deref(), Some("http://localhost:3000/users"));
+            }
+        });
+    });
+
+    assert_eq!(
+        rewrite(&rules, "https://real-api.com/api/0/0/users").as_deref(),
+        Some("http://localhost:3000/0/0/users")
+    );
+}
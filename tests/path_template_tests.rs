@@ -0,0 +1,222 @@
+mod common;
+
+use common::test_utils::{build_rules, rewrite};
+use httpmock::prelude::*;
+use reqwest_rewire::{RewireClient, RewireError, RewireRules, TestableClientExt};
+
+#[test]
+fn test_named_segment_is_substituted() {
+    let rules = build_rules(&[(
+        "https://api.com/users/{id}",
+        "http://mock/fixtures/user-{id}.json",
+    )]);
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/users/42"),
+        Some("http://mock/fixtures/user-42.json".to_string())
+    );
+    assert_eq!(rewrite(&rules, "https://api.com/users"), None);
+}
+
+#[test]
+fn test_several_named_segments_are_substituted() {
+    let rules = build_rules(&[(
+        "https://api.com/users/{id}/orders/{order_id}",
+        "http://mock/fixtures/{id}/order-{order_id}.json",
+    )]);
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/users/42/orders/7?expand=items"),
+        Some("http://mock/fixtures/42/order-7.json?expand=items".to_string())
+    );
+}
+
+#[test]
+fn test_named_segment_keeps_prefix_semantics() {
+    let rules = build_rules(&[("https://api.com/users/{id}/", "http://mock/users/{id}/")]);
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/users/42/orders/7"),
+        Some("http://mock/users/42/orders/7".to_string())
+    );
+}
+
+#[test]
+fn test_rest_segment_captures_remaining_path() {
+    let rules = build_rules(&[(
+        "https://cdn.com/assets/{path*}",
+        "http://mock/static/{path}?cached=1",
+    )]);
+
+    assert_eq!(
+        rewrite(&rules, "https://cdn.com/assets/img/logo.png"),
+        Some("http://mock/static/img/logo.png?cached=1".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, "https://cdn.com/assets"),
+        Some("http://mock/static/?cached=1".to_string())
+    );
+}
+
+#[test]
+fn test_rest_segment_can_be_written_with_star_in_target() {
+    let rules = build_rules(&[("https://cdn.com/{path*}", "http://mock/{path*}.json")]);
+
+    assert_eq!(
+        rewrite(&rules, "https://cdn.com/a/b"),
+        Some("http://mock/a/b.json".to_string())
+    );
+}
+
+#[test]
+fn test_literal_segments_beat_named_segments() {
+    let rules = build_rules(&[
+        ("https://api.com/users/{id}", "http://mock/user-{id}.json"),
+        ("https://api.com/users/me", "http://mock/me.json"),
+        ("https://api.com/{rest*}", "http://mock/fallback/{rest}"),
+    ]);
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/users/me"),
+        Some("http://mock/me.json".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, "https://api.com/users/42"),
+        Some("http://mock/user-42.json".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, "https://api.com/orders/7"),
+        Some("http://mock/fallback/orders/7".to_string())
+    );
+}
+
+#[test]
+fn test_named_segment_combines_with_wildcard_host() {
+    let rules = build_rules(&[(
+        "https://*.api.com/users/{id}",
+        "http://mock/{1}/user-{id}.json",
+    )]);
+
+    assert_eq!(
+        rewrite(&rules, "https://acme.api.com/users/42"),
+        Some("http://mock/acme/user-42.json".to_string())
+    );
+}
+
+#[test]
+fn test_template_rules_can_be_removed() {
+    let rules = build_rules(&[("https://api.com/users/{id}", "http://mock/user-{id}.json")]);
+
+    assert!(rules.remove_rule("https://api.com/users/{id}"));
+
+    assert_eq!(rewrite(&rules, "https://api.com/users/42"), None);
+}
+
+#[test]
+fn test_renamed_segment_is_a_duplicate_rule() {
+    let error = RewireRules::builder()
+        .rewire("https://api.com/users/{id}", "http://mock/a/{id}")
+        .rewire("https://api.com/users/{uid}", "http://mock/b/{uid}")
+        .build()
+        .err()
+        .unwrap();
+
+    assert!(matches!(error, RewireError::DuplicateRule { .. }));
+}
+
+#[test]
+fn test_rest_segment_must_come_last() {
+    let error = RewireRules::builder()
+        .rewire("https://api.com/{rest*}/users", "http://mock/")
+        .build()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        RewireError::InvalidTemplate {
+            url: "https://api.com/{rest*}/users".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_repeated_segment_name_is_rejected() {
+    let error = RewireRules::builder()
+        .rewire("https://api.com/{id}/{id}", "http://mock/{id}")
+        .build()
+        .err()
+        .unwrap();
+
+    assert!(matches!(error, RewireError::InvalidTemplate { .. }));
+}
+
+#[test]
+fn test_uncaptured_segment_name_is_rejected() {
+    let error = RewireRules::builder()
+        .rewire("https://api.com/users/{id}", "http://mock/{order_id}")
+        .build()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        RewireError::UnknownPlaceholder {
+            target: "http://mock/{order_id}".to_string(),
+            placeholder: "order_id".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_rule_does_not_match_when_filled_target_is_invalid() {
+    let rules = build_rules(&[("https://api.com/{h}", "http://{h}.mock/")]);
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/tenant"),
+        Some("http://tenant.mock/".to_string())
+    );
+    assert_eq!(rewrite(&rules, "https://api.com/a%20b"), None);
+}
+
+#[test]
+fn test_rule_does_not_match_when_empty_capture_leaves_empty_segment() {
+    let rules = build_rules(&[("https://api.com/files/{p*}", "http://mock/{p}/x")]);
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/files/a/b"),
+        Some("http://mock/a/b/x".to_string())
+    );
+    assert_eq!(rewrite(&rules, "https://api.com/files"), None);
+}
+
+#[test]
+fn test_empty_rest_capture_at_end_of_target_matches() {
+    let rules = build_rules(&[("https://api.com/files/{p*}", "http://mock/{p*}")]);
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/files"),
+        Some("http://mock/".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_client_sends_request_to_fixture() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/fixtures/user-42.json");
+        then.status(200);
+    });
+    let client = RewireClient::builder()
+        .rewire(
+            "https://api.com/users/{id}",
+            server.url("/fixtures/user-{id}.json"),
+        )
+        .build()
+        .unwrap();
+
+    let response = client.get("https://api.com/users/42").send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
@@ -0,0 +1,243 @@
+#![cfg(feature = "regex")]
+
+mod common;
+
+use common::test_utils::rewrite;
+use httpmock::prelude::*;
+use reqwest_rewire::{RewireClient, RewireError, RewireRules, Rule, TestableClientExt};
+
+const VERSION_PATTERN: &str = r"https://api\.com/v(\d+)/(.*)";
+const VERSION_REPLACEMENT: &str = "http://localhost:3000/$2?version=$1";
+
+fn version_rules() -> RewireRules {
+    RewireRules::builder()
+        .rewire_regex(VERSION_PATTERN, VERSION_REPLACEMENT)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_regex_captures_are_substituted() {
+    let rules = version_rules();
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/v2/users/42"),
+        Some("http://localhost:3000/users/42?version=2".to_string())
+    );
+}
+
+#[test]
+fn test_regex_named_captures_are_substituted() {
+    let rules = RewireRules::builder()
+        .rewire_regex(
+            r"https://(?<tenant>\w+)\.api\.com/(?<rest>.*)",
+            "http://localhost:3000/${tenant}/${rest}",
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(&rules, "https://acme.api.com/users"),
+        Some("http://localhost:3000/acme/users".to_string())
+    );
+}
+
+#[test]
+fn test_regex_keeps_request_query_and_fragment() {
+    let rules = version_rules();
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/v2/users?page=1#top"),
+        Some("http://localhost:3000/users?version=2&page=1#top".to_string())
+    );
+}
+
+#[test]
+fn test_regex_must_match_the_whole_url() {
+    let rules = RewireRules::builder()
+        .rewire_regex(r"https://api\.com/v\d+", "http://localhost:3000/")
+        .build()
+        .unwrap();
+
+    assert_eq!(rewrite(&rules, "https://api.com/v2/users"), None);
+    assert_eq!(
+        rewrite(&rules, "https://api.com/v2"),
+        Some("http://localhost:3000/".to_string())
+    );
+}
+
+#[test]
+fn test_regex_rules_take_precedence_over_prefix_rules() {
+    let rules = RewireRules::builder()
+        .rewire("https://api.com/v2/users/", "http://localhost:3001/")
+        .rewire("https://api.com/", "http://localhost:3002/")
+        .rewire_regex(VERSION_PATTERN, VERSION_REPLACEMENT)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/v2/users/42"),
+        Some("http://localhost:3000/users/42?version=2".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, "https://api.com/users"),
+        Some("http://localhost:3002/users".to_string())
+    );
+}
+
+#[test]
+fn test_rule_regex_registers_a_regex_rule() {
+    let rules = RewireRules::builder()
+        .rule(Rule::regex(VERSION_PATTERN, VERSION_REPLACEMENT))
+        .rule(Rule::prefix("https://api.com/", "http://localhost:3002/"))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/v2/users/42"),
+        Some("http://localhost:3000/users/42?version=2".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, "https://api.com/users"),
+        Some("http://localhost:3002/users".to_string())
+    );
+}
+
+#[test]
+fn test_active_override_takes_precedence_over_regex_rules() {
+    let rules = RewireRules::builder()
+        .rewire("https://api.com/", "http://localhost:3002/")
+        .rewire_regex(VERSION_PATTERN, VERSION_REPLACEMENT)
+        .build()
+        .unwrap();
+
+    let guard = rules
+        .override_rule("https://api.com/", "http://localhost:3003/")
+        .unwrap();
+    assert_eq!(
+        rewrite(&rules, "https://api.com/v2/users/42"),
+        Some("http://localhost:3003/v2/users/42".to_string())
+    );
+
+    drop(guard);
+    assert_eq!(
+        rewrite(&rules, "https://api.com/v2/users/42"),
+        Some("http://localhost:3000/users/42?version=2".to_string())
+    );
+}
+
+#[test]
+fn test_regex_rules_take_precedence_over_longer_overridden_prefixes() {
+    let rules = RewireRules::builder()
+        .rewire("https://api.com/v2/", "http://localhost:3002/")
+        .rewire_regex(VERSION_PATTERN, VERSION_REPLACEMENT)
+        .build()
+        .unwrap();
+
+    // The request uses the longer `v2` rule, which is not overridden, so the regex still wins.
+    let _guard = rules
+        .override_rule("https://api.com/", "http://localhost:3003/")
+        .unwrap();
+    assert_eq!(
+        rewrite(&rules, "https://api.com/v2/users/42"),
+        Some("http://localhost:3000/users/42?version=2".to_string())
+    );
+}
+
+#[test]
+fn test_first_matching_regex_rule_wins() {
+    let rules = RewireRules::builder()
+        .rewire_regex(r"https://api\.com/.*", "http://localhost:3000/")
+        .rewire_regex(VERSION_PATTERN, VERSION_REPLACEMENT)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/v2/users"),
+        Some("http://localhost:3000/".to_string())
+    );
+}
+
+#[test]
+fn test_invalid_regex_is_rejected() {
+    let error = RewireRules::builder()
+        .rewire_regex(r"https://api\.com/(", "http://localhost:3000/")
+        .build()
+        .err()
+        .unwrap();
+
+    assert!(matches!(
+        error,
+        RewireError::InvalidRegex { pattern, .. } if pattern == r"https://api\.com/("
+    ));
+}
+
+#[test]
+fn test_duplicate_regex_is_rejected_in_strict_mode() {
+    let builder = RewireRules::builder()
+        .rewire_regex(VERSION_PATTERN, VERSION_REPLACEMENT)
+        .rewire_regex(VERSION_PATTERN, "http://localhost:3001/$2");
+
+    let error = builder.build().err().unwrap();
+
+    assert!(matches!(error, RewireError::DuplicateRule { .. }));
+}
+
+#[test]
+fn test_duplicate_regex_replaces_earlier_one_in_lenient_mode() {
+    let rules = RewireRules::builder()
+        .rewire_regex(VERSION_PATTERN, VERSION_REPLACEMENT)
+        .rewire_regex(VERSION_PATTERN, "http://localhost:3001/$2")
+        .strict(false)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rewrite(&rules, "https://api.com/v2/users"),
+        Some("http://localhost:3001/users".to_string())
+    );
+}
+
+#[test]
+fn test_regex_rules_can_be_added_and_removed() {
+    let rules = RewireRules::default();
+
+    rules
+        .add_regex_rule(VERSION_PATTERN, VERSION_REPLACEMENT)
+        .unwrap();
+    assert!(rewrite(&rules, "https://api.com/v2/users").is_some());
+
+    assert!(rules.remove_regex_rule(VERSION_PATTERN));
+    assert!(!rules.remove_regex_rule(VERSION_PATTERN));
+    assert_eq!(rewrite(&rules, "https://api.com/v2/users"), None);
+}
+
+#[tokio::test]
+async fn test_client_sends_request_to_regex_target() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/users/42")
+            .query_param("version", "2");
+        then.status(200);
+    });
+    let client = RewireClient::builder()
+        .rewire_regex(VERSION_PATTERN, server.url("/$2?version=$1"))
+        .journal(true)
+        .build()
+        .unwrap();
+
+    let response = client
+        .get("https://api.com/v2/users/42")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+    client.assert_all_rules_used();
+    assert_eq!(
+        client.journal().unwrap().entries()[0].rule.as_deref(),
+        Some(VERSION_PATTERN)
+    );
+}
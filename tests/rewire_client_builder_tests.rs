@@ -0,0 +1,107 @@
+mod common;
+
+use common::test_utils::*;
+use httpmock::MockServer;
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest_rewire::{RewireClient, RewireError, TestableClientExt};
+
+#[tokio::test]
+async fn test_builder_get_request_rewire() {
+    let server = MockServer::start();
+    let client = RewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .build()
+        .unwrap();
+    test_get(client, None, true, &server).await;
+}
+
+#[tokio::test]
+async fn test_builder_request_with_nested_path_rewire() {
+    let server = MockServer::start();
+    let client = RewireClient::builder()
+        .rewires(build_rewire_test_params(&server))
+        .build()
+        .unwrap();
+    test_request_with_nested_path(client, true, &server, http::Method::GET).await;
+}
+
+#[tokio::test]
+async fn test_builder_without_rules() {
+    let server = MockServer::start();
+    let client = RewireClient::builder().build().unwrap();
+    test_get(client, None, false, &server).await;
+}
+
+#[tokio::test]
+async fn test_builder_with_reqwest_client() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.path("/rewire_request")
+            .header("user-agent", "rewire-tests");
+        then.status(200);
+    });
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static("rewire-tests"));
+    let reqwest_client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    let client = RewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .with_reqwest_client(reqwest_client)
+        .build()
+        .unwrap();
+    let response = client.get(server.url("/request")).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[test]
+fn test_builder_is_strict_by_default() {
+    let error = RewireClient::builder()
+        .rewire("https://api.example.com/api", "http://localhost:3000/mock")
+        .rewire("not a url", "http://localhost:3000/")
+        .build()
+        .err();
+    assert!(matches!(error, Some(RewireError::InvalidSource { .. })));
+}
+
+#[test]
+fn test_builder_strict_rejects_duplicates() {
+    let error = RewireClient::builder()
+        .rewire("https://api.example.com/api", "http://localhost:3000/a")
+        .rewire("https://api.example.com/api", "http://localhost:3000/b")
+        .build()
+        .err();
+    assert!(matches!(error, Some(RewireError::DuplicateRule { .. })));
+}
+
+#[test]
+fn test_builder_non_strict_ignores_invalid_rules() {
+    let client = RewireClient::builder()
+        .rewire("not a url", "http://localhost:3000/")
+        .rewire("https://api.example.com/api", "http://localhost:3000/mock")
+        .strict(false)
+        .build()
+        .unwrap();
+    assert_eq!(
+        rewired_url(&client, "https://api.example.com/api/users"),
+        "http://localhost:3000/mock/users"
+    );
+}
+
+#[test]
+fn test_builder_non_strict_keeps_last_duplicate() {
+    let client = RewireClient::builder()
+        .rewire("https://api.example.com/api", "http://localhost:3000/a")
+        .rewire("https://api.example.com/api", "http://localhost:3000/b")
+        .strict(false)
+        .build()
+        .unwrap();
+    assert_eq!(
+        rewired_url(&client, "https://api.example.com/api"),
+        "http://localhost:3000/b"
+    );
+}
@@ -0,0 +1,85 @@
+use reqwest_rewire::{RewireClient, RewireError};
+use std::collections::HashMap;
+
+fn try_new(rules: &[(&str, &str)]) -> Result<RewireClient, RewireError> {
+    let redirects = rules
+        .iter()
+        .map(|(source, target)| (source.to_string(), target.to_string()))
+        .collect::<HashMap<_, _>>();
+    RewireClient::try_new(redirects)
+}
+
+#[test]
+fn test_try_new_with_valid_rules() {
+    let client = try_new(&[
+        (
+            "https://api.example.com/api/",
+            "http://localhost:3000/mock/",
+        ),
+        (
+            "https://api.example.com/api/v2/",
+            "http://localhost:3000/mock-v2/",
+        ),
+    ]);
+    assert!(client.is_ok());
+}
+
+#[test]
+fn test_try_new_with_unparseable_source() {
+    let error = try_new(&[("not a url", "http://localhost:3000/mock/")]).err();
+    assert!(matches!(
+        error,
+        Some(RewireError::InvalidSource { url, .. }) if url == "not a url"
+    ));
+}
+
+#[test]
+fn test_try_new_with_unparseable_target() {
+    let error = try_new(&[("https://api.example.com/", "http://[::1")]).err();
+    assert!(matches!(
+        error,
+        Some(RewireError::InvalidTarget { url, .. }) if url == "http://[::1"
+    ));
+}
+
+#[test]
+fn test_try_new_with_cannot_be_a_base_target() {
+    let error = try_new(&[("https://api.example.com/", "mailto:mock@example.com")]).err();
+    assert!(matches!(error, Some(RewireError::CannotBeABase { .. })));
+}
+
+#[test]
+fn test_try_new_with_cannot_be_a_base_source() {
+    let error = try_new(&[("data:text/plain,api", "http://localhost:3000/")]).err();
+    assert!(matches!(error, Some(RewireError::CannotBeABase { .. })));
+}
+
+#[test]
+fn test_try_new_with_source_without_host() {
+    let error = try_new(&[("file:///etc/api", "http://localhost:3000/")]).err();
+    assert!(matches!(error, Some(RewireError::MissingHost { .. })));
+}
+
+#[test]
+fn test_try_new_with_duplicate_rules() {
+    let error = try_new(&[
+        ("https://api.example.com/api", "http://localhost:3000/a/"),
+        (
+            "https://api.example.com:443/api/",
+            "http://localhost:3000/b/",
+        ),
+    ])
+    .err();
+    assert!(matches!(error, Some(RewireError::DuplicateRule { .. })));
+}
+
+#[test]
+fn test_rewire_error_display() {
+    let error = try_new(&[("https://api.example.com/", "http://[::1")])
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "invalid rule target `http://[::1`: invalid IPv6 address"
+    );
+}
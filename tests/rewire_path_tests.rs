@@ -0,0 +1,163 @@
+mod common;
+
+use common::test_utils::rewired_url as client_rewired_url;
+use proptest::prelude::*;
+use reqwest_rewire::RewireClient;
+use std::sync::OnceLock;
+
+fn rewired_url(source: &str, target: &str, url: &str) -> String {
+    // Building a reqwest client is costly, so property cases share one.
+    static REQWEST_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    let client = RewireClient::builder()
+        .rewire(source, target)
+        .with_reqwest_client(REQWEST_CLIENT.get_or_init(reqwest::Client::new).clone())
+        .build()
+        .unwrap();
+    client_rewired_url(&client, url)
+}
+
+fn path(segments: &[String], trailing_slash: bool) -> String {
+    let mut path = format!("/{}", segments.join("/"));
+    if trailing_slash && !segments.is_empty() {
+        path.push('/');
+    }
+    path
+}
+
+fn segments() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec("[a-z0-9_-]{1,8}", 0..4)
+}
+
+#[test]
+fn test_deeper_target_than_source() {
+    assert_eq!(
+        rewired_url(
+            "https://api.com/v1/",
+            "http://localhost/mocks/api/v1/",
+            "https://api.com/v1/users/42"
+        ),
+        "http://localhost/mocks/api/v1/users/42"
+    );
+}
+
+#[test]
+fn test_shallower_target_than_source() {
+    assert_eq!(
+        rewired_url(
+            "https://api.com/mocks/api/v1/",
+            "http://localhost/v1/",
+            "https://api.com/mocks/api/v1/users/42"
+        ),
+        "http://localhost/v1/users/42"
+    );
+}
+
+#[test]
+fn test_root_source_and_target() {
+    assert_eq!(
+        rewired_url(
+            "https://api.com/",
+            "http://localhost/",
+            "https://api.com/users"
+        ),
+        "http://localhost/users"
+    );
+    assert_eq!(
+        rewired_url("https://api.com", "http://localhost", "https://api.com"),
+        "http://localhost/"
+    );
+}
+
+#[test]
+fn test_exact_match_uses_target_as_written() {
+    assert_eq!(
+        rewired_url(
+            "https://api.com/api/",
+            "http://localhost/mock",
+            "https://api.com/api"
+        ),
+        "http://localhost/mock"
+    );
+    assert_eq!(
+        rewired_url(
+            "https://api.com/api",
+            "http://localhost/mock/",
+            "https://api.com/api"
+        ),
+        "http://localhost/mock/"
+    );
+}
+
+#[test]
+fn test_request_trailing_slash_is_kept() {
+    assert_eq!(
+        rewired_url(
+            "https://api.com/api",
+            "http://localhost/mock",
+            "https://api.com/api/"
+        ),
+        "http://localhost/mock/"
+    );
+    assert_eq!(
+        rewired_url(
+            "https://api.com/api",
+            "http://localhost/mock/",
+            "https://api.com/api/users/"
+        ),
+        "http://localhost/mock/users/"
+    );
+}
+
+proptest! {
+    #[test]
+    fn prop_remainder_is_appended_to_target(
+        source in segments(),
+        target in segments(),
+        remainder in prop::collection::vec("[a-z0-9_-]{1,8}", 1..4),
+        source_slash in any::<bool>(),
+        target_slash in any::<bool>(),
+        request_slash in any::<bool>(),
+    ) {
+        let request = [source.clone(), remainder.clone()].concat();
+        let expected = [target.clone(), remainder].concat();
+
+        let rewired = rewired_url(
+            &format!("https://api.com{}", path(&source, source_slash)),
+            &format!("http://localhost:3000{}", path(&target, target_slash)),
+            &format!("https://api.com{}", path(&request, request_slash)),
+        );
+
+        prop_assert_eq!(rewired, format!("http://localhost:3000{}", path(&expected, request_slash)));
+    }
+
+    #[test]
+    fn prop_exact_match_uses_target_path(
+        source in segments(),
+        target in segments(),
+        source_slash in any::<bool>(),
+        target_slash in any::<bool>(),
+    ) {
+        let rewired = rewired_url(
+            &format!("https://api.com{}", path(&source, source_slash)),
+            &format!("http://localhost:3000{}", path(&target, target_slash)),
+            &format!("https://api.com{}", path(&source, false)),
+        );
+
+        prop_assert_eq!(rewired, format!("http://localhost:3000{}", path(&target, target_slash)));
+    }
+
+    #[test]
+    fn prop_unrelated_prefix_is_not_rewired(
+        source in prop::collection::vec("[a-z]{1,8}", 1..4),
+        request in prop::collection::vec("[0-9]{1,8}", 1..4),
+    ) {
+        let url = format!("https://api.com{}", path(&request, false));
+        let rewired = rewired_url(
+            &format!("https://api.com{}", path(&source, false)),
+            "http://localhost:3000/mock",
+            &url,
+        );
+
+        prop_assert_eq!(rewired, url);
+    }
+}
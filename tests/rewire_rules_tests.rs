@@ -0,0 +1,192 @@
+mod common;
+
+use common::test_utils::rewired_url;
+use httpmock::prelude::*;
+use reqwest_rewire::{RewireClient, RewireRules, TestableClientExt};
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn test_most_specific_rule_wins_among_many() {
+    let server = MockServer::start();
+    let mut redirects = HashMap::new();
+    for i in 0..500 {
+        redirects.insert(server.url(format!("/unused/{}", i)), server.url("/wrong"));
+    }
+    redirects.insert(server.url("/api"), server.url("/wrong"));
+    redirects.insert(server.url("/api/v1"), server.url("/rewire/request"));
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/rewire/request/users");
+        then.status(200);
+    });
+
+    let client = RewireClient::new(redirects);
+    let response = client
+        .get(server.url("/api/v1/users"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_rule_matches_whole_path_segments() {
+    let server = MockServer::start();
+    let mut redirects = HashMap::new();
+    redirects.insert(server.url("/api"), server.url("/rewire_request"));
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/apifoo");
+        then.status(200);
+    });
+
+    let client = RewireClient::new(redirects);
+    let response = client.get(server.url("/apifoo")).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_invalid_rules_are_ignored() {
+    let server = MockServer::start();
+    let mut redirects = HashMap::new();
+    redirects.insert("not a url".to_string(), server.url("/wrong"));
+    redirects.insert(server.url("/request"), "not a url either".to_string());
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/request");
+        then.status(200);
+    });
+
+    let client = RewireClient::new(redirects);
+    let response = client.get(server.url("/request")).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[test]
+fn test_rule_does_not_match_other_port() {
+    let mut redirects = HashMap::new();
+    redirects.insert(
+        "http://localhost:8080/api".to_string(),
+        "http://localhost:3000/mock-a".to_string(),
+    );
+    redirects.insert(
+        "http://localhost:9090/api".to_string(),
+        "http://localhost:3001/mock-b".to_string(),
+    );
+    let client = RewireClient::new(redirects);
+
+    assert_eq!(
+        rewired_url(&client, "http://localhost:8080/api"),
+        "http://localhost:3000/mock-a"
+    );
+    assert_eq!(
+        rewired_url(&client, "http://localhost:9090/api"),
+        "http://localhost:3001/mock-b"
+    );
+    assert_eq!(
+        rewired_url(&client, "http://localhost:7070/api"),
+        "http://localhost:7070/api"
+    );
+}
+
+#[test]
+fn test_rule_does_not_match_other_scheme() {
+    let mut redirects = HashMap::new();
+    redirects.insert(
+        "https://api.example.com/api".to_string(),
+        "http://localhost:3000/mock".to_string(),
+    );
+    let client = RewireClient::new(redirects);
+
+    assert_eq!(
+        rewired_url(&client, "https://api.example.com/api"),
+        "http://localhost:3000/mock"
+    );
+    assert_eq!(
+        rewired_url(&client, "http://api.example.com/api"),
+        "http://api.example.com/api"
+    );
+}
+
+#[test]
+fn test_rule_with_explicit_default_port_matches_implicit_port() {
+    let mut redirects = HashMap::new();
+    redirects.insert(
+        "https://api.example.com:443/api".to_string(),
+        "http://localhost:3000/mock".to_string(),
+    );
+    redirects.insert(
+        "http://api.example.com/api".to_string(),
+        "http://localhost:3001/mock".to_string(),
+    );
+    let client = RewireClient::new(redirects);
+
+    assert_eq!(
+        rewired_url(&client, "https://api.example.com/api"),
+        "http://localhost:3000/mock"
+    );
+    assert_eq!(
+        rewired_url(&client, "http://api.example.com:80/api"),
+        "http://localhost:3001/mock"
+    );
+    assert_eq!(
+        rewired_url(&client, "https://api.example.com:8443/api"),
+        "https://api.example.com:8443/api"
+    );
+}
+
+#[test]
+fn test_rules_rewrite_uri() {
+    let rules = RewireRules::builder()
+        .rewire(
+            "https://real-api.com/api/",
+            "http://localhost:3000/api-mock/",
+        )
+        .build()
+        .unwrap();
+    let uri: http::Uri = "https://real-api.com/api/v1/users?id=42".parse().unwrap();
+
+    assert_eq!(
+        rules.rewrite_uri(&uri).unwrap(),
+        "http://localhost:3000/api-mock/v1/users?id=42"
+    );
+}
+
+#[test]
+fn test_rules_rewrite_uri_without_match() {
+    let rules = RewireRules::builder()
+        .rewire(
+            "https://real-api.com/api/",
+            "http://localhost:3000/api-mock/",
+        )
+        .build()
+        .unwrap();
+
+    assert!(
+        rules
+            .rewrite_uri(&"https://other-api.com/api/".parse().unwrap())
+            .is_none()
+    );
+    assert!(rules.rewrite_uri(&"/api/users".parse().unwrap()).is_none());
+}
+
+#[test]
+fn test_client_rules_are_shared() {
+    let client = RewireClient::builder()
+        .rewire(
+            "https://real-api.com/api/",
+            "http://localhost:3000/api-mock/",
+        )
+        .build()
+        .unwrap();
+    let rules = client.rules().clone();
+    let url = reqwest::Url::parse("https://real-api.com/api/users").unwrap();
+
+    assert_eq!(
+        rules.rewrite(&url).unwrap().as_str(),
+        "http://localhost:3000/api-mock/users"
+    );
+}
@@ -0,0 +1,223 @@
+mod common;
+
+use common::test_utils::rewrite;
+use httpmock::prelude::*;
+use reqwest::Url;
+use reqwest_rewire::{BlockingRewireClient, RewireClient, RewireError, TestableClientExt};
+
+fn build_client() -> RewireClient {
+    RewireClient::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/")
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_override_is_restored_when_guard_drops() {
+    let server = MockServer::start();
+    let failing = server.mock(|when, then| {
+        when.method(GET).path("/failing/users");
+        then.status(500);
+    });
+    let working = server.mock(|when, then| {
+        when.method(GET).path("/working/users");
+        then.status(200);
+    });
+    let client = RewireClient::builder()
+        .rewire("https://real-api.com/api/", server.url("/working/"))
+        .build()
+        .unwrap();
+
+    {
+        let _guard = client
+            .override_rule("https://real-api.com/api/", &server.url("/failing/"))
+            .unwrap();
+        let response = client
+            .get("https://real-api.com/api/users")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 500);
+    }
+    let response = client
+        .get("https://real-api.com/api/users")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    failing.assert();
+    working.assert();
+}
+
+#[test]
+fn test_override_without_existing_rule() {
+    let client = build_client();
+
+    let guard = client
+        .override_rule("https://auth.real-api.com/", "http://localhost:3001/")
+        .unwrap();
+    assert_eq!(
+        rewrite(client.rules(), "https://auth.real-api.com/login").as_deref(),
+        Some("http://localhost:3001/login")
+    );
+    assert_eq!(guard.source(), "https://auth.real-api.com/");
+
+    drop(guard);
+    assert_eq!(rewrite(client.rules(), "https://auth.real-api.com/login"), None);
+}
+
+#[test]
+fn test_override_matches_equivalent_source() {
+    let client = build_client();
+
+    let _guard = client
+        .override_rule("https://real-api.com:443/api", "http://localhost:3500/")
+        .unwrap();
+
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/users").as_deref(),
+        Some("http://localhost:3500/users")
+    );
+}
+
+#[test]
+fn test_nested_overrides_dropped_out_of_order() {
+    let client = build_client();
+
+    let first = client
+        .override_rule("https://real-api.com/api/", "http://localhost:3501/")
+        .unwrap();
+    let second = client
+        .override_rule("https://real-api.com/api/", "http://localhost:3502/")
+        .unwrap();
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/").as_deref(),
+        Some("http://localhost:3502/")
+    );
+
+    drop(first);
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/").as_deref(),
+        Some("http://localhost:3502/")
+    );
+
+    drop(second);
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/").as_deref(),
+        Some("http://localhost:3000/")
+    );
+}
+
+#[test]
+fn test_more_specific_rule_wins_over_override() {
+    let client = RewireClient::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/")
+        .rewire("https://real-api.com/api/v1/", "http://localhost:3001/")
+        .build()
+        .unwrap();
+
+    let _guard = client
+        .override_rule("https://real-api.com/api/", "http://localhost:3500/")
+        .unwrap();
+
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/v1/users").as_deref(),
+        Some("http://localhost:3001/users")
+    );
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/v2/users").as_deref(),
+        Some("http://localhost:3500/v2/users")
+    );
+}
+
+#[test]
+fn test_override_is_shared_with_clones() {
+    let client = build_client();
+    let clone = client.clone();
+
+    let guard = client
+        .override_rule("https://real-api.com/api/", "http://localhost:3500/")
+        .unwrap();
+    assert_eq!(
+        rewrite(clone.rules(), "https://real-api.com/api/").as_deref(),
+        Some("http://localhost:3500/")
+    );
+
+    drop(guard);
+    assert_eq!(
+        rewrite(clone.rules(), "https://real-api.com/api/").as_deref(),
+        Some("http://localhost:3000/")
+    );
+}
+
+#[test]
+fn test_remove_rule_keeps_override() {
+    let client = build_client();
+    let guard = client
+        .override_rule("https://real-api.com/api/", "http://localhost:3500/")
+        .unwrap();
+
+    assert!(client.remove_rule("https://real-api.com/api/"));
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/").as_deref(),
+        Some("http://localhost:3500/")
+    );
+
+    drop(guard);
+    assert_eq!(rewrite(client.rules(), "https://real-api.com/api/"), None);
+}
+
+#[test]
+fn test_guard_dropped_after_clear_keeps_new_rules() {
+    let client = build_client();
+    let guard = client
+        .override_rule("https://real-api.com/api/", "http://localhost:3500/")
+        .unwrap();
+
+    client.clear();
+    client
+        .add_rule("https://real-api.com/api/", "http://localhost:4000/")
+        .unwrap();
+    drop(guard);
+
+    assert_eq!(
+        rewrite(client.rules(), "https://real-api.com/api/").as_deref(),
+        Some("http://localhost:4000/")
+    );
+}
+
+#[test]
+fn test_invalid_override() {
+    let client = build_client();
+
+    let error = client
+        .override_rule("https://real-api.com/api/", "not a url")
+        .err()
+        .unwrap();
+
+    assert!(matches!(error, RewireError::InvalidTarget { .. }));
+}
+
+#[test]
+fn test_blocking_override() {
+    let client = BlockingRewireClient::builder()
+        .rewire("https://real-api.com/api/", "http://localhost:3000/")
+        .build()
+        .unwrap();
+    let url = Url::parse("https://real-api.com/api/").unwrap();
+
+    let guard = client
+        .override_rule("https://real-api.com/api/", "http://localhost:3500/")
+        .unwrap();
+    assert_eq!(
+        client.rules().rewrite(&url).unwrap().as_str(),
+        "http://localhost:3500/"
+    );
+
+    drop(guard);
+    assert_eq!(
+        client.rules().rewrite(&url).unwrap().as_str(),
+        "http://localhost:3000/"
+    );
+}
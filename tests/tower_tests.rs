@@ -0,0 +1,226 @@
+#![cfg(feature = "tower")]
+
+use http::header::HOST;
+use http::{Method, Request, Uri};
+use reqwest_rewire::{RewireClient, RewireError, RewireLayer};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use tower::{Layer, ServiceBuilder, ServiceExt, service_fn};
+
+/// Sends `request` through `layer` to a service echoing the request it receives.
+async fn forward(layer: &RewireLayer, request: Request<()>) -> Request<()> {
+    let echo = service_fn(|request: Request<()>| async move { Ok::<_, Infallible>(request) });
+    layer.layer(echo).oneshot(request).await.unwrap()
+}
+
+fn build_layer() -> RewireLayer {
+    RewireLayer::builder()
+        .rewire(
+            "https://real-api.com/api/",
+            "http://localhost:3000/api-mock/",
+        )
+        .build_layer()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_absolute_uri_rewire() {
+    let request = Request::get("https://real-api.com/api/users?id=42")
+        .body(())
+        .unwrap();
+
+    let request = forward(&build_layer(), request).await;
+
+    assert_eq!(request.uri(), "http://localhost:3000/api-mock/users?id=42");
+}
+
+#[tokio::test]
+async fn test_method_rule_applies_to_its_method_only() {
+    let layer = RewireLayer::builder()
+        .rewire_methods(
+            [Method::POST],
+            "https://real-api.com/api/",
+            "http://localhost:3001/fail/",
+        )
+        .build_layer()
+        .unwrap();
+
+    let post = forward(
+        &layer,
+        Request::post("https://real-api.com/api/users")
+            .body(())
+            .unwrap(),
+    )
+    .await;
+    let get = forward(
+        &layer,
+        Request::get("https://real-api.com/api/users")
+            .body(())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(post.uri(), "http://localhost:3001/fail/users");
+    assert_eq!(get.uri(), "https://real-api.com/api/users");
+}
+
+#[tokio::test]
+async fn test_header_rule_applies_to_requests_carrying_the_header() {
+    let layer = RewireLayer::builder()
+        .rewire_headers(
+            [("x-tenant", "acme")],
+            "https://real-api.com/api/",
+            "http://localhost:3001/acme/",
+        )
+        .build_layer()
+        .unwrap();
+
+    let tenant = Request::get("https://real-api.com/api/users")
+        .header("x-tenant", "acme")
+        .body(())
+        .unwrap();
+    let tenant = forward(&layer, tenant).await;
+    let other = forward(
+        &layer,
+        Request::get("https://real-api.com/api/users")
+            .body(())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(tenant.uri(), "http://localhost:3001/acme/users");
+    assert_eq!(other.uri(), "https://real-api.com/api/users");
+}
+
+#[tokio::test]
+async fn test_unmatched_uri_is_unchanged() {
+    let request = Request::get("https://other-api.com/api/users")
+        .header(HOST, "other-api.com")
+        .body(())
+        .unwrap();
+
+    let request = forward(&build_layer(), request).await;
+
+    assert_eq!(request.uri(), "https://other-api.com/api/users");
+    assert_eq!(request.headers()[HOST], "other-api.com");
+}
+
+#[tokio::test]
+async fn test_host_header_follows_rewire() {
+    let request = Request::get("https://real-api.com/api/users")
+        .header(HOST, "real-api.com")
+        .body(())
+        .unwrap();
+
+    let request = forward(&build_layer(), request).await;
+
+    assert_eq!(request.uri(), "http://localhost:3000/api-mock/users");
+    assert_eq!(request.headers()[HOST], "localhost:3000");
+}
+
+#[tokio::test]
+async fn test_host_header_is_not_added() {
+    let request = Request::get("https://real-api.com/api/users")
+        .body(())
+        .unwrap();
+
+    let request = forward(&build_layer(), request).await;
+
+    assert!(!request.headers().contains_key(HOST));
+}
+
+#[tokio::test]
+async fn test_origin_form_uri_is_matched_with_host_header() {
+    let layer = RewireLayer::builder()
+        .rewire(
+            "http://real-api.com/api/",
+            "http://localhost:3000/api-mock/",
+        )
+        .build_layer()
+        .unwrap();
+    let request = Request::get("/api/users?id=42")
+        .header(HOST, "real-api.com")
+        .body(())
+        .unwrap();
+
+    let request = forward(&layer, request).await;
+
+    assert_eq!(request.uri(), "/api-mock/users?id=42");
+    assert_eq!(request.headers()[HOST], "localhost:3000");
+}
+
+#[tokio::test]
+async fn test_origin_form_uri_without_host_header_is_unchanged() {
+    let request = Request::get("/api/users").body(()).unwrap();
+
+    let request = forward(&build_layer(), request).await;
+
+    assert_eq!(request.uri(), "/api/users");
+}
+
+#[tokio::test]
+async fn test_layer_shares_client_rules() {
+    let client = RewireClient::builder()
+        .rewire(
+            "https://real-api.com/api/",
+            "http://localhost:3000/api-mock/",
+        )
+        .build()
+        .unwrap();
+    let layer = RewireLayer::from_rules(client.rules().clone());
+    let request = Request::get("https://real-api.com/api/users")
+        .body(())
+        .unwrap();
+
+    let request = forward(&layer, request).await;
+
+    assert_eq!(request.uri(), "http://localhost:3000/api-mock/users");
+}
+
+#[tokio::test]
+async fn test_layer_composes_with_service_builder() {
+    let service = ServiceBuilder::new()
+        .layer(build_layer())
+        .map_request(|request: Request<()>| request.uri().clone())
+        .service_fn(|uri: Uri| async move { Ok::<_, Infallible>(uri) });
+
+    let uri = service
+        .oneshot(Request::get("https://real-api.com/api/").body(()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(uri, "http://localhost:3000/api-mock/");
+}
+
+#[test]
+fn test_try_new_rejects_invalid_rule() {
+    let mut redirects = HashMap::new();
+    redirects.insert(
+        "not a url".to_string(),
+        "http://localhost:3000/".to_string(),
+    );
+
+    let error = RewireLayer::try_new(redirects).err().unwrap();
+
+    assert!(matches!(error, RewireError::InvalidSource { .. }));
+}
+
+#[tokio::test]
+async fn test_new_ignores_invalid_rules() {
+    let mut redirects = HashMap::new();
+    redirects.insert(
+        "not a url".to_string(),
+        "http://localhost:3000/".to_string(),
+    );
+    redirects.insert(
+        "https://real-api.com/api/".to_string(),
+        "http://localhost:3000/api-mock/".to_string(),
+    );
+    let request = Request::get("https://real-api.com/api/users")
+        .body(())
+        .unwrap();
+
+    let request = forward(&RewireLayer::new(redirects), request).await;
+
+    assert_eq!(request.uri(), "http://localhost:3000/api-mock/users");
+}
@@ -0,0 +1,175 @@
+use httpmock::prelude::*;
+use reqwest_rewire::{
+    BlockingRewireClient, BlockingTestableClientExt, Error, RewireClient, RewireError,
+    TestableClientExt, UnmatchedPolicy,
+};
+
+fn build_client(server: &MockServer, policy: UnmatchedPolicy) -> RewireClient {
+    RewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .unmatched(policy)
+        .build()
+        .unwrap()
+}
+
+fn assert_unmatched(error: &Error, url: &str) {
+    assert!(matches!(
+        error,
+        Error::Rewire(RewireError::Unmatched { url: unmatched }) if unmatched == url
+    ));
+}
+
+#[tokio::test]
+async fn test_unmatched_request_is_allowed_by_default() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/unmatched");
+        then.status(200);
+    });
+    let client = RewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .build()
+        .unwrap();
+
+    let response = client.get(server.url("/unmatched")).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_unmatched_request_fails_without_being_sent() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/unmatched");
+        then.status(200);
+    });
+    let client = build_client(&server, UnmatchedPolicy::Error);
+
+    let error = client
+        .get(server.url("/unmatched"))
+        .send()
+        .await
+        .unwrap_err();
+
+    assert_unmatched(&error, &server.url("/unmatched"));
+    mock.assert_calls(0);
+}
+
+#[tokio::test]
+async fn test_matched_request_is_sent_in_error_mode() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/rewire_request");
+        then.status(200);
+    });
+    let client = build_client(&server, UnmatchedPolicy::Error);
+
+    let response = client.get(server.url("/request")).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_unmatched_request_to_allowed_host_is_sent() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/unmatched");
+        then.status(200);
+    });
+    let client = RewireClient::builder()
+        .rewire("https://real-api.com/api/", server.url("/rewire_request"))
+        .unmatched(UnmatchedPolicy::Error)
+        .allow_host(server.host())
+        .build()
+        .unwrap();
+
+    let response = client.get(server.url("/unmatched")).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_allowed_host_is_case_insensitive() {
+    let client = RewireClient::builder()
+        .unmatched(UnmatchedPolicy::Error)
+        .allow_host("LocalHost")
+        .build()
+        .unwrap();
+
+    assert!(client.get("http://localhost:1/").build().is_ok());
+}
+
+#[tokio::test]
+async fn test_unmatched_execute_fails() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/unmatched");
+        then.status(200);
+    });
+    let client = build_client(&server, UnmatchedPolicy::Error);
+    let url = reqwest::Url::parse(&server.url("/unmatched")).unwrap();
+
+    let error = client
+        .execute(reqwest::Request::new(http::Method::GET, url))
+        .await
+        .unwrap_err();
+
+    assert_unmatched(&error, &server.url("/unmatched"));
+    mock.assert_calls(0);
+}
+
+#[tokio::test]
+#[should_panic(expected = "no rule matches")]
+async fn test_unmatched_request_panics() {
+    let server = MockServer::start();
+    let client = build_client(&server, UnmatchedPolicy::Panic);
+
+    let _ = client.get(server.url("/unmatched")).send().await;
+}
+
+#[test]
+fn test_blocking_unmatched_request_fails_without_being_sent() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/unmatched");
+        then.status(200);
+    });
+    let client = BlockingRewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .unmatched(UnmatchedPolicy::Error)
+        .build()
+        .unwrap();
+
+    let error = client.get(server.url("/unmatched")).send().unwrap_err();
+
+    assert_unmatched(&error, &server.url("/unmatched"));
+    mock.assert_calls(0);
+}
+
+#[test]
+fn test_unmatched_error_message() {
+    let error = RewireError::Unmatched {
+        url: "https://real-api.com/api/users".to_string(),
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "no rule matches `https://real-api.com/api/users`, so it was not sent"
+    );
+    assert_eq!(Error::Rewire(error.clone()).to_string(), error.to_string());
+}
+
+#[tokio::test]
+async fn test_invalid_url_fails_with_reqwest_error() {
+    let client = RewireClient::builder()
+        .unmatched(UnmatchedPolicy::Error)
+        .build()
+        .unwrap();
+
+    let error = client.get("not a url").send().await.unwrap_err();
+
+    assert!(matches!(error, Error::Reqwest(error) if error.is_builder()));
+}
@@ -0,0 +1,119 @@
+use httpmock::prelude::*;
+use reqwest_rewire::{
+    BlockingRewireClient, BlockingTestableClientExt, RewireClient, TestableClientExt,
+    UnusedRulePolicy,
+};
+use std::panic::{self, AssertUnwindSafe};
+
+fn build_client(server: &MockServer, policy: UnusedRulePolicy) -> RewireClient {
+    RewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .rewire("https://auth.real-api.com/", server.url("/auth-mock"))
+        .unused_rules(policy)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_unused_rules_are_ignored_by_default() {
+    let server = MockServer::start();
+
+    drop(
+        RewireClient::builder()
+            .rewire(server.url("/request"), server.url("/rewire_request"))
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "these rules never matched a request:")]
+fn test_unused_rules_panic_on_drop() {
+    let server = MockServer::start();
+
+    drop(build_client(&server, UnusedRulePolicy::Panic));
+}
+
+#[tokio::test]
+async fn test_used_rules_are_not_reported() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.any_request();
+        then.status(200);
+    });
+    let client = build_client(&server, UnusedRulePolicy::Panic);
+
+    client.get(server.url("/request")).send().await.unwrap();
+    client
+        .get("https://auth.real-api.com/token")
+        .send()
+        .await
+        .unwrap();
+
+    drop(client);
+}
+
+#[tokio::test]
+async fn test_unused_rules_are_reported_when_the_last_clone_is_dropped() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.any_request();
+        then.status(200);
+    });
+    let client = build_client(&server, UnusedRulePolicy::Panic);
+    let clone = client.clone();
+
+    drop(client);
+    clone.get(server.url("/request")).send().await.unwrap();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| drop(clone)));
+
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(
+        *message,
+        "these rules never matched a request:\n    https://auth.real-api.com/"
+    );
+}
+
+#[test]
+fn test_unused_rules_only_warn_when_warn_policy() {
+    let server = MockServer::start();
+
+    drop(build_client(&server, UnusedRulePolicy::Warn));
+}
+
+#[test]
+fn test_unused_rules_do_not_panic_during_a_panic() {
+    let server = MockServer::start();
+    let client = build_client(&server, UnusedRulePolicy::Panic);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(move || {
+        let _client = client;
+        panic!("original failure");
+    }));
+
+    assert_eq!(
+        *result.unwrap_err().downcast::<&str>().unwrap(),
+        "original failure"
+    );
+}
+
+#[test]
+#[should_panic(expected = "these rules never matched a request:\n    http://added.com/")]
+fn test_rules_added_at_runtime_are_reported() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.any_request();
+        then.status(200);
+    });
+    let client = BlockingRewireClient::builder()
+        .rewire(server.url("/request"), server.url("/rewire_request"))
+        .unused_rules(UnusedRulePolicy::Panic)
+        .build()
+        .unwrap();
+    client
+        .add_rule("http://added.com/", "http://localhost/")
+        .unwrap();
+
+    client.get(server.url("/request")).send().unwrap();
+    drop(client);
+}
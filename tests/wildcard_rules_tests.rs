@@ -0,0 +1,187 @@
+mod common;
+
+use common::test_utils::{build_rules, rewrite};
+use httpmock::prelude::*;
+use reqwest_rewire::{RewireClient, RewireError, RewireRules, TestableClientExt};
+
+#[test]
+fn test_wildcard_matches_any_label() {
+    let rules = build_rules(&[("https://*.api.example.com/", "http://localhost:3000/")]);
+
+    assert_eq!(
+        rewrite(&rules, "https://acme.api.example.com/users"),
+        Some("http://localhost:3000/users".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, "https://foo.api.example.com/users"),
+        Some("http://localhost:3000/users".to_string())
+    );
+}
+
+#[test]
+fn test_wildcard_matches_exactly_one_label() {
+    let rules = build_rules(&[("https://*.api.example.com/", "http://localhost:3000/")]);
+
+    assert_eq!(rewrite(&rules, "https://api.example.com/users"), None);
+    assert_eq!(rewrite(&rules, "https://a.b.api.example.com/users"), None);
+}
+
+#[test]
+fn test_wildcard_requires_same_scheme_and_port() {
+    let rules = build_rules(&[("https://*.api.example.com/", "http://localhost:3000/")]);
+
+    assert_eq!(rewrite(&rules, "http://acme.api.example.com/"), None);
+    assert_eq!(rewrite(&rules, "https://acme.api.example.com:8443/"), None);
+    assert!(rewrite(&rules, "https://acme.api.example.com:443/").is_some());
+}
+
+#[test]
+fn test_captured_labels_are_substituted() {
+    let rules = build_rules(&[
+        (
+            "https://*.api.example.com/",
+            "http://localhost:3000/tenants/{1}/",
+        ),
+        ("https://*.*.example.org/", "http://{2}.localhost/{1}"),
+    ]);
+
+    assert_eq!(
+        rewrite(&rules, "https://acme.api.example.com/users?id=42"),
+        Some("http://localhost:3000/tenants/acme/users?id=42".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, "https://eu.acme.example.org/users"),
+        Some("http://acme.localhost/eu/users".to_string())
+    );
+}
+
+#[test]
+fn test_exact_host_beats_wildcard() {
+    let rules = build_rules(&[
+        (
+            "https://*.api.example.com/v1/users",
+            "http://localhost:3000/",
+        ),
+        ("https://acme.api.example.com/", "http://localhost:3001/"),
+    ]);
+
+    assert_eq!(
+        rewrite(&rules, "https://acme.api.example.com/v1/users"),
+        Some("http://localhost:3001/v1/users".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, "https://foo.api.example.com/v1/users"),
+        Some("http://localhost:3000/".to_string())
+    );
+}
+
+#[test]
+fn test_wildcard_applies_when_exact_host_has_no_matching_path() {
+    let rules = build_rules(&[
+        ("https://*.api.example.com/", "http://localhost:3000/"),
+        ("https://acme.api.example.com/v2/", "http://localhost:3001/"),
+    ]);
+
+    assert_eq!(
+        rewrite(&rules, "https://acme.api.example.com/v1/users"),
+        Some("http://localhost:3000/v1/users".to_string())
+    );
+}
+
+#[test]
+fn test_wildcard_with_most_literal_labels_wins() {
+    let rules = build_rules(&[
+        ("https://*.*.example.com/", "http://localhost:3000/"),
+        ("https://*.api.example.com/", "http://localhost:3001/"),
+    ]);
+
+    assert_eq!(
+        rewrite(&rules, "https://acme.api.example.com/"),
+        Some("http://localhost:3001/".to_string())
+    );
+    assert_eq!(
+        rewrite(&rules, "https://acme.web.example.com/"),
+        Some("http://localhost:3000/".to_string())
+    );
+}
+
+#[test]
+fn test_wildcard_rules_can_be_removed() {
+    let rules = build_rules(&[("https://*.api.example.com/", "http://localhost:3000/")]);
+
+    assert!(rules.remove_rule("https://*.api.example.com"));
+
+    assert_eq!(rewrite(&rules, "https://acme.api.example.com/"), None);
+}
+
+#[test]
+fn test_partial_wildcard_label_is_rejected() {
+    let error = RewireRules::builder()
+        .rewire(
+            "https://tenant-*.api.example.com/",
+            "http://localhost:3000/",
+        )
+        .build()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        RewireError::InvalidWildcard {
+            url: "https://tenant-*.api.example.com/".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_uncaptured_placeholder_is_rejected() {
+    let error = RewireRules::builder()
+        .rewire("https://*.api.example.com/", "http://localhost:3000/{2}/")
+        .build()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        RewireError::UnknownPlaceholder {
+            target: "http://localhost:3000/{2}/".to_string(),
+            placeholder: "2".to_string()
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "rule target `http://localhost:3000/{2}/` uses `{2}`, which its source does not capture"
+    );
+}
+
+#[test]
+fn test_braces_are_literal_without_captures() {
+    let rules = build_rules(&[("https://api.example.com/", "http://localhost:3000/?q={x}")]);
+
+    assert_eq!(
+        rewrite(&rules, "https://api.example.com/users"),
+        Some("http://localhost:3000/users?q={x}".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_client_rewires_tenant_subdomains() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/tenants/acme/users");
+        then.status(200);
+    });
+    let client = RewireClient::builder()
+        .rewire("http://*.tenants.test/", server.url("/tenants/{1}/"))
+        .build()
+        .unwrap();
+
+    let response = client
+        .get("http://acme.tenants.test/users")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    mock.assert();
+}